@@ -0,0 +1,34 @@
+package burst.kit.crypto;
+
+import java.nio.ByteBuffer;
+
+/**
+ * Native methods of libshabal, built with the {@code jni} cargo feature.
+ *
+ * <p>Load the library first, eg. with {@code System.loadLibrary("shabal")}. Arrays of the wrong length throw
+ * {@link IllegalArgumentException} and null arrays throw {@link NullPointerException}.
+ */
+public final class LibShabal {
+    private LibShabal() {
+    }
+
+    /**
+     * Finds the best deadline among {@code nonceCount} scoops in a direct buffer, storing the deadline
+     * (unsigned) in {@code result[0]} and the offset of its nonce in {@code result[1]}.
+     */
+    public static native void findBestDeadlineDirect(ByteBuffer scoops, long nonceCount, byte[] gensig, long[] result);
+
+    /** {@link #findBestDeadlineDirect} for scoops in an array. */
+    public static native void findBestDeadline(byte[] scoops, long nonceCount, byte[] gensig, long[] result);
+
+    public static native byte[] shabal256(byte[] data);
+
+    public static native byte[] getPublicKey(byte[] privateKey);
+
+    public static native byte[] getSharedSecret(byte[] privateKey, byte[] publicKey);
+
+    /** Signs the SHA-256 hash of a message, returning the 64 byte signature. */
+    public static native byte[] sign(byte[] privateKey, byte[] messageSha256);
+
+    public static native boolean verify(byte[] publicKey, byte[] signature, byte[] messageSha256, boolean enforceCanonical);
+}
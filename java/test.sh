@@ -0,0 +1,12 @@
+#!/bin/bash
+# Builds the library with the jni feature and runs the Java checks of its bindings against it.
+
+set -e
+cd "$(dirname "$0")/.."
+
+cargo build --features "jni"
+
+library=$(ls target/debug/libshabal.so target/debug/libshabal.dylib 2>/dev/null | head -n 1)
+classes=target/java-test
+javac -d $classes java/burst/kit/crypto/LibShabal.java java/test/burst/kit/crypto/LibShabalTest.java
+java -cp $classes burst.kit.crypto.LibShabalTest "$PWD/$library"
@@ -0,0 +1,83 @@
+package burst.kit.crypto;
+
+import java.nio.ByteBuffer;
+import java.util.Arrays;
+
+/**
+ * Checks the JNI bindings against a built library, whose path is the only argument. Run by {@code java/test.sh}.
+ */
+public final class LibShabalTest {
+    private LibShabalTest() {
+    }
+
+    public static void main(String[] args) {
+        System.load(args[0]);
+
+        byte[] scoops = new byte[64 * 100];
+        for (int i = 0; i < scoops.length; i++) {
+            scoops[i] = (byte) (i * 31 + 7);
+        }
+        byte[] gensig = new byte[32];
+        long[] result = new long[2];
+        long[] directResult = new long[2];
+        LibShabal.findBestDeadline(scoops, 100, gensig, result);
+        ByteBuffer direct = ByteBuffer.allocateDirect(scoops.length);
+        direct.put(scoops);
+        LibShabal.findBestDeadlineDirect(direct, 100, gensig, directResult);
+        check(Arrays.equals(result, directResult), "both deadline searches agree");
+        check(result[1] >= 0 && result[1] < 100, "the offset is one of the nonces");
+
+        check(Arrays.equals(LibShabal.shabal256(new byte[0]), hex("aec750d11feee9f16271922fbaf5a9be142f62019ef8d720f858940070889014")),
+                "shabal256 of nothing");
+
+        byte[] privateKey = new byte[32];
+        Arrays.fill(privateKey, (byte) 0x42);
+        byte[] publicKey = LibShabal.getPublicKey(privateKey);
+        byte[] messageSha256 = new byte[32];
+        byte[] signature = LibShabal.sign(privateKey, messageSha256);
+        check(LibShabal.verify(publicKey, signature, messageSha256, true), "a signature verifies");
+        signature[40] ^= 1;
+        check(!LibShabal.verify(publicKey, signature, messageSha256, true), "a tampered signature does not verify");
+
+        byte[] otherPrivateKey = new byte[32];
+        Arrays.fill(otherPrivateKey, (byte) 0xFF);
+        byte[] sharedSecret = LibShabal.getSharedSecret(privateKey, LibShabal.getPublicKey(otherPrivateKey));
+        check(Arrays.equals(sharedSecret, LibShabal.getSharedSecret(otherPrivateKey, publicKey)), "both sides agree on the shared secret");
+
+        expect(IllegalArgumentException.class, () -> LibShabal.sign(new byte[31], messageSha256));
+        expect(IllegalArgumentException.class, () -> LibShabal.verify(publicKey, new byte[63], messageSha256, true));
+        expect(NullPointerException.class, () -> LibShabal.verify(null, signature, messageSha256, true));
+        expect(NullPointerException.class, () -> LibShabal.shabal256(null));
+        expect(IllegalArgumentException.class, () -> LibShabal.findBestDeadline(scoops, 101, gensig, result));
+        expect(IllegalArgumentException.class, () -> LibShabal.findBestDeadline(scoops, -1, gensig, result));
+        expect(IllegalArgumentException.class, () -> LibShabal.findBestDeadline(scoops, 1, gensig, new long[1]));
+        expect(IllegalArgumentException.class, () -> LibShabal.findBestDeadlineDirect(ByteBuffer.wrap(scoops), 1, gensig, result));
+        expect(NullPointerException.class, () -> LibShabal.findBestDeadlineDirect(null, 1, gensig, result));
+
+        System.out.println("LibShabal JNI bindings OK");
+    }
+
+    private static void check(boolean condition, String description) {
+        if (!condition) {
+            throw new AssertionError(description);
+        }
+    }
+
+    private static void expect(Class<? extends Exception> exception, Runnable call) {
+        try {
+            call.run();
+        } catch (Exception e) {
+            check(exception.isInstance(e), "expected " + exception.getName() + " but got " + e);
+            return;
+        }
+        throw new AssertionError("expected " + exception.getName());
+    }
+
+    private static byte[] hex(String hex) {
+        byte[] bytes = new byte[hex.length() / 2];
+        for (int i = 0; i < bytes.length; i++) {
+            bytes[i] = (byte) Integer.parseInt(hex.substring(i * 2, i * 2 + 2), 16);
+        }
+        return bytes;
+    }
+}
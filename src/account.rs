@@ -0,0 +1,80 @@
+use crate::curve25519;
+use crate::keys::{PrivateKey, SigningKey};
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The Burst account id of `public_key`: the first 8 bytes of its SHA-256 hash, little endian.
+pub fn account_id_from_public_key(public_key: &[u8; 32]) -> u64 {
+    let hash = Sha256::digest(public_key);
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&hash[..8]);
+    return u64::from_le_bytes(id);
+}
+
+/// The keys of a Burst account.
+#[derive(Debug, Clone)]
+pub struct Keypair {
+    pub public_key: [u8; 32],
+    /// The EC-KCDSA signing key, for signers which should hold only a key that signs (`SigningKey::sign`).
+    pub signing_key: SigningKey,
+    /// The clamped private key, for key agreement (`PrivateKey::shared_secret`). It also signs, exactly as
+    /// `signing_key` does, since that is derived from it.
+    pub agreement_key: PrivateKey,
+}
+
+/// Derives the keys of `passphrase` the way the Burst wallet does:
+/// the private key is the SHA-256 hash of the passphrase, which is clamped and then used for key generation.
+pub fn keypair_from_passphrase(passphrase: &[u8]) -> Keypair {
+    let mut hash = Sha256::digest(passphrase);
+    let mut private_key = Zeroizing::new([0u8; 32]);
+    private_key.copy_from_slice(&hash);
+    hash.as_mut_slice().zeroize();
+    curve25519::clamp(&mut private_key);
+    let agreement_key = PrivateKey::from_bytes(&private_key);
+    return Keypair { public_key: agreement_key.public_key(), signing_key: agreement_key.signing_key(), agreement_key };
+}
+
+/// The public key of `passphrase`.
+pub fn public_key_from_passphrase(passphrase: &[u8]) -> [u8; 32] {
+    return keypair_from_passphrase(passphrase).public_key;
+}
+
+/// The Burst account id of `passphrase`.
+pub fn account_id_from_passphrase(passphrase: &[u8]) -> u64 {
+    return account_id_from_public_key(&public_key_from_passphrase(passphrase));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn account_id_known_answer() {
+        let public_key = public_key_from_passphrase(b"correct horse battery staple");
+        assert_eq!(public_key, [
+            0x8f, 0x14, 0x52, 0x7a, 0x1d, 0xf1, 0xed, 0x2b, 0x41, 0x8a, 0xdf, 0x4c, 0x9b, 0xc7, 0x1a, 0x9a,
+            0xed, 0x4e, 0x74, 0x28, 0x36, 0xf5, 0xc5, 0xa9, 0xc7, 0x01, 0x58, 0xbb, 0x6b, 0xcd, 0xc7, 0x77,
+        ]);
+        assert_eq!(account_id_from_public_key(&public_key), 11623955783251610612);
+        assert_eq!(account_id_from_passphrase(b"correct horse battery staple"), 11623955783251610612);
+    }
+
+    #[test]
+    fn keypair_signs_and_agrees() {
+        let keypair = keypair_from_passphrase(b"correct horse battery staple");
+        assert_eq!(keypair.public_key, public_key_from_passphrase(b"correct horse battery staple"));
+        assert_eq!(keypair.agreement_key.as_bytes()[0] & 7, 0);
+        assert_eq!(keypair.agreement_key.as_bytes()[31] & 0xC0, 0x40);
+        assert_eq!(keypair.agreement_key.public_key(), keypair.public_key);
+
+        let mut message_sha256 = [0u8; 32];
+        message_sha256.copy_from_slice(&Sha256::digest(b"message"));
+        let signature = keypair.signing_key.sign(&message_sha256);
+        assert!(curve25519::verify(&keypair.public_key, &signature, &message_sha256, true));
+        assert_eq!(signature[..], keypair.agreement_key.sign(&message_sha256)[..]);
+
+        let other = keypair_from_passphrase(b"another passphrase");
+        let shared_secret = keypair.agreement_key.shared_secret(&other.public_key);
+        assert_eq!(shared_secret.as_bytes(), other.agreement_key.shared_secret(&keypair.public_key).as_bytes());
+    }
+}
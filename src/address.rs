@@ -0,0 +1,197 @@
+//! Burst Reed-Solomon addresses such as `BURST-2222-2222-2222-22222`.
+//!
+//! An address is the account id in base 32 (13 symbols) followed by 4 Reed-Solomon parity symbols over GF(32),
+//! shuffled and written in groups of 4, 4, 4 and 5 symbols. The parity symbols let up to 2 wrong
+//! symbols be corrected, which `decode` reports instead of silently accepting the corrected id.
+
+use std::fmt;
+
+const ALPHABET: &[u8; 32] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+const GEXP: [u8; 32] = [
+    1, 2, 4, 8, 16, 5, 10, 20, 13, 26, 17, 7, 14, 28, 29, 31, 27, 19, 3, 6, 12, 24, 21, 15, 30, 25, 23, 11, 22, 9, 18, 1,
+];
+const GLOG: [u8; 32] = [
+    0, 0, 1, 18, 2, 5, 19, 11, 3, 29, 6, 27, 20, 8, 12, 23, 4, 10, 30, 17, 7, 22, 28, 26, 21, 25, 9, 16, 13, 14, 24, 15,
+];
+/// Position in the codeword of each symbol of the address.
+const CODEWORD_MAP: [usize; CODEWORD_LENGTH] = [3, 2, 1, 0, 7, 6, 5, 4, 13, 14, 15, 16, 12, 8, 9, 10, 11];
+const DATA_LENGTH: usize = 13;
+const CODEWORD_LENGTH: usize = 17;
+
+/// Prefix of addresses on the Burst main net.
+pub const BURST_PREFIX: &str = "BURST";
+
+/// Longest encoded address in bytes, without its prefix.
+pub const ADDRESS_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    /// The address does not have 17 symbols after its prefix.
+    InvalidLength,
+    /// The address contains a character which is not part of the address alphabet.
+    InvalidCharacter(char),
+    /// The parity symbols do not match.
+    ///
+    /// `corrected` is the account id of the only valid address at most 2 symbols away, if there is one.
+    InvalidChecksum { corrected: Option<u64> },
+    /// The address is valid but does not fit in 64 bits.
+    Overflow,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            AddressError::InvalidLength => write!(f, "an address has 17 symbols"),
+            AddressError::InvalidCharacter(c) => write!(f, "'{}' is not a valid address symbol", c),
+            AddressError::InvalidChecksum { corrected: Some(corrected) } => {
+                write!(f, "invalid address checksum, did you mean {}?", encode(*corrected, None))
+            }
+            AddressError::InvalidChecksum { corrected: None } => write!(f, "invalid address checksum"),
+            AddressError::Overflow => write!(f, "the address is out of range of an account id"),
+        };
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+/// Encodes `account_id` as an address, eg. `BURST-2222-2222-2222-22222` with prefix `BURST`.
+///
+/// Without a prefix just the symbols are returned, eg. `2222-2222-2222-22222`.
+/// A trailing `-` on `prefix` is optional.
+pub fn encode(account_id: u64, prefix: Option<&str>) -> String {
+    let mut codeword = [0u8; CODEWORD_LENGTH];
+    for (i, symbol) in codeword[..DATA_LENGTH].iter_mut().enumerate() {
+        *symbol = ((account_id >> (5 * i)) & 31) as u8;
+    }
+
+    let mut parity = [0u8; CODEWORD_LENGTH - DATA_LENGTH];
+    for i in (0..DATA_LENGTH).rev() {
+        let feedback = codeword[i] ^ parity[3];
+        parity[3] = parity[2] ^ gmult(30, feedback);
+        parity[2] = parity[1] ^ gmult(6, feedback);
+        parity[1] = parity[0] ^ gmult(9, feedback);
+        parity[0] = gmult(17, feedback);
+    }
+    codeword[DATA_LENGTH..].copy_from_slice(&parity);
+
+    let mut address = String::new();
+    if let Some(prefix) = prefix {
+        address.push_str(prefix.trim_end_matches('-'));
+        address.push('-');
+    }
+    for (i, &index) in CODEWORD_MAP.iter().enumerate() {
+        address.push(ALPHABET[codeword[index] as usize] as char);
+        if i % 4 == 3 && i < DATA_LENGTH {
+            address.push('-');
+        }
+    }
+    return address;
+}
+
+/// Decodes an address with or without a prefix, returning the account id.
+///
+/// Letters may be lower case and surrounding whitespace is ignored.
+pub fn decode(address: &str) -> Result<u64, AddressError> {
+    let address = address.trim().to_ascii_uppercase();
+    let groups: Vec<&str> = address.split('-').collect();
+    // anything in front of the 4 groups of symbols is the prefix
+    let symbols = if groups.len() > 4 { groups[groups.len() - 4..].concat() } else { groups.concat() };
+
+    let mut codeword = [0u8; CODEWORD_LENGTH];
+    let mut length = 0;
+    for c in symbols.chars() {
+        let symbol = ALPHABET.iter().position(|&a| a as char == c).ok_or(AddressError::InvalidCharacter(c))?;
+        if length == CODEWORD_LENGTH { return Err(AddressError::InvalidLength); }
+        codeword[CODEWORD_MAP[length]] = symbol as u8;
+        length += 1;
+    }
+    if length != CODEWORD_LENGTH { return Err(AddressError::InvalidLength); }
+
+    if !is_codeword_valid(&codeword) {
+        let corrected = correct(&codeword).and_then(|corrected| account_id(&corrected).ok());
+        return Err(AddressError::InvalidChecksum { corrected });
+    }
+    return account_id(&codeword);
+}
+
+fn account_id(codeword: &[u8; CODEWORD_LENGTH]) -> Result<u64, AddressError> {
+    // the 13 symbols hold 65 bits, of which the top one has to be clear
+    if codeword[DATA_LENGTH - 1] >= 16 { return Err(AddressError::Overflow); }
+    return Ok(codeword[..DATA_LENGTH].iter().rev().fold(0u64, |id, &symbol| (id << 5) | symbol as u64));
+}
+
+/// Finds the codeword at most 2 symbols away from `codeword`, which is unique if it exists.
+fn correct(codeword: &[u8; CODEWORD_LENGTH]) -> Option<[u8; CODEWORD_LENGTH]> {
+    let mut candidate = *codeword;
+    for first in 0..CODEWORD_LENGTH {
+        for first_symbol in 0..32 {
+            candidate[first] = first_symbol;
+            if is_codeword_valid(&candidate) { return Some(candidate); }
+            for second in first + 1..CODEWORD_LENGTH {
+                for second_symbol in 0..32 {
+                    candidate[second] = second_symbol;
+                    if is_codeword_valid(&candidate) { return Some(candidate); }
+                }
+                candidate[second] = codeword[second];
+            }
+        }
+        candidate[first] = codeword[first];
+    }
+    return None;
+}
+
+fn is_codeword_valid(codeword: &[u8; CODEWORD_LENGTH]) -> bool {
+    let mut sum = 0;
+    for i in 1..5 {
+        let mut t = 0;
+        for j in 0..31 {
+            if j > 12 && j < 27 { continue; }
+            let position = if j > 26 { j - 14 } else { j };
+            t ^= gmult(codeword[position], GEXP[(i * j) % 31]);
+        }
+        sum |= t;
+    }
+    return sum == 0;
+}
+
+fn gmult(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 { return 0; }
+    return GEXP[(GLOG[a as usize] as usize + GLOG[b as usize] as usize) % 31];
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn known_addresses() {
+        assert_eq!(encode(0, Some(BURST_PREFIX)), "BURST-2222-2222-2222-22222");
+        assert_eq!(encode(1739068987193023818, None), "MRCC-2YLS-8M54-3CMAJ");
+        assert_eq!(encode(u64::MAX, Some("TS-")), "TS-ZZZZ-ZZZZ-QY2K-HZZZZ");
+        assert_eq!(decode("BURST-MRCC-2YLS-8M54-3CMAJ"), Ok(1739068987193023818));
+        assert_eq!(decode(" s-mrcc-2yls-8m54-3cmaj "), Ok(1739068987193023818));
+        assert_eq!(decode("MRCC2YLS8M543CMAJ"), Ok(1739068987193023818));
+        assert_eq!(decode("TS-ZZZZ-ZZZZ-QY2K-HZZZZ"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn invalid_addresses() {
+        assert_eq!(decode("BURST-MRCC-2YLS-8M54-3CMA"), Err(AddressError::InvalidLength));
+        assert_eq!(decode("BURST-MRCC-2YLS-8M54-3CMAJJ"), Err(AddressError::InvalidLength));
+        assert_eq!(decode("BURST-MRCC-2YLS-8M54-3CMA0"), Err(AddressError::InvalidCharacter('0')));
+        // one and two wrong symbols are corrected, their positions don't matter
+        let corrected = Some(1739068987193023818);
+        assert_eq!(decode("BURST-MRCD-2YLS-8M54-3CMAJ"), Err(AddressError::InvalidChecksum { corrected }));
+        assert_eq!(decode("BURST-MRCC-2YLS-8M54-3CM22"), Err(AddressError::InvalidChecksum { corrected }));
+        assert_eq!(decode("BURST-2RCC-2YLS-8M54-3CMA2"), Err(AddressError::InvalidChecksum { corrected }));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut account_id = 0x9E3779B97F4A7C15u64;
+        for _ in 0..1000 {
+            account_id = account_id.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            assert_eq!(decode(&encode(account_id, Some(BURST_PREFIX))), Ok(account_id));
+        }
+    }
+}
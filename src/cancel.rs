@@ -0,0 +1,49 @@
+use std::os::raw::c_void;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Progress callback used by the C ABI.
+///
+/// Called with the number of nonces processed so far and the `user_data` pointer supplied by the caller.
+pub type ProgressCallback = Option<extern "C" fn(processed: u64, user_data: *mut c_void)>;
+
+/// A shareable flag which long-running operations poll between batches of work.
+///
+/// Clones share the same flag, so one clone can be handed to the worker and another
+/// kept by whoever needs to stop it (eg. when a new block arrives).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicU8>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        return CancellationToken::default();
+    }
+
+    /// Requests that any operation using this token stops as soon as possible.
+    pub fn cancel(&self) {
+        self.cancelled.store(1, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed) != 0;
+    }
+
+    /// The underlying flag, the same `uint8_t` the C kernels poll. Any non-zero value means cancelled.
+    pub fn flag(&self) -> &AtomicU8 {
+        return &self.cancelled;
+    }
+}
+
+/// Returns the raw pointer handed to the C kernels for an optional flag.
+pub(crate) fn flag_ptr(flag: Option<&AtomicU8>) -> *const u8 {
+    return match flag {
+        Some(flag) => flag as *const AtomicU8 as *const u8,
+        None => std::ptr::null(),
+    };
+}
+
+pub(crate) fn is_set(flag: Option<&AtomicU8>) -> bool {
+    return flag.is_some_and(|flag| flag.load(Ordering::Relaxed) != 0);
+}
@@ -0,0 +1,248 @@
+//! Arithmetic in GF(2^255 - 19), with numbers held in 10 signed limbs of alternately 26 and 25 bits.
+//!
+//! Numbers are in reduced form as output by `unpack`, `mul`, `sqr` and `mul_small`. `add` and `sub` accept
+//! reduced numbers only and their output is not reduced, so it has to go through a multiplication (by 1 if
+//! need be) before another addition or subtraction.
+
+const P25: i64 = (1 << 25) - 1;
+const P26: i64 = (1 << 26) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldElement(pub [i64; 10]);
+
+impl FieldElement {
+    pub const ZERO: FieldElement = FieldElement([0; 10]);
+}
+
+/// Converts from 32 little endian bytes, split into limbs of 26, 25, 26, 25, 26, 25, 26, 25, 26 and 26 bits.
+pub fn unpack(m: &[u8; 32]) -> FieldElement {
+    let m = |i: usize| m[i] as i64;
+    return FieldElement([
+        m(0) | m(1) << 8 | m(2) << 16 | (m(3) & 3) << 24,
+        (m(3) & !3) >> 2 | m(4) << 6 | m(5) << 14 | (m(6) & 7) << 22,
+        (m(6) & !7) >> 3 | m(7) << 5 | m(8) << 13 | (m(9) & 31) << 21,
+        (m(9) & !31) >> 5 | m(10) << 3 | m(11) << 11 | (m(12) & 63) << 19,
+        (m(12) & !63) >> 6 | m(13) << 2 | m(14) << 10 | m(15) << 18,
+        m(16) | m(17) << 8 | m(18) << 16 | (m(19) & 1) << 24,
+        (m(19) & !1) >> 1 | m(20) << 7 | m(21) << 15 | (m(22) & 7) << 23,
+        (m(22) & !7) >> 3 | m(23) << 5 | m(24) << 13 | (m(25) & 15) << 21,
+        (m(25) & !15) >> 4 | m(26) << 4 | m(27) << 12 | (m(28) & 63) << 20,
+        (m(28) & !63) >> 6 | m(29) << 2 | m(30) << 10 | m(31) << 18,
+    ]);
+}
+
+/// Converts a reduced number to 32 little endian bytes, fully reduced modulo 2^255 - 19.
+pub fn pack(number: &FieldElement) -> [u8; 32] {
+    let x = &number.0;
+    let mut ld = is_overflow(number) - (x[9] < 0) as i64;
+    let ud = ld * -(P25 + 1);
+    ld *= 19;
+
+    let mut m = [0u8; 32];
+    let mut t = ld + x[0] + (x[1] << 26);
+    m[0..4].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + (x[2] << 19);
+    m[4..8].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + (x[3] << 13);
+    m[8..12].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + (x[4] << 6);
+    m[12..16].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + x[5] + (x[6] << 25);
+    m[16..20].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + (x[7] << 19);
+    m[20..24].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + (x[8] << 12);
+    m[24..28].copy_from_slice(&(t as u32).to_le_bytes());
+    t = (t >> 32) + ((x[9] + ud) << 6);
+    m[28..32].copy_from_slice(&(t as u32).to_le_bytes());
+    return m;
+}
+
+/// 1 if a reduced number is at least 2^255 - 19, otherwise 0. Branch free.
+pub fn is_overflow(x: &FieldElement) -> i64 {
+    let x = &x.0;
+    return ((x[0] > P26 - 19) as i64
+        & ((x[1] & x[3] & x[5] & x[7] & x[9]) == P25) as i64
+        & ((x[2] & x[4] & x[6] & x[8]) == P26) as i64)
+        | (x[9] > P25) as i64;
+}
+
+/// 1 if a reduced number is "negative", ie. odd once fully reduced, otherwise 0. Branch free.
+pub fn is_negative(x: &FieldElement) -> i64 {
+    return (is_overflow(x) | (x.0[9] < 0) as i64) ^ (x.0[0] & 1);
+}
+
+/// A number in the range -185861411 to 185861411.
+pub fn set(value: i64) -> FieldElement {
+    let mut x = FieldElement::ZERO;
+    x.0[0] = value;
+    return x;
+}
+
+pub fn add(x: &FieldElement, y: &FieldElement) -> FieldElement {
+    let mut xy = FieldElement::ZERO;
+    for i in 0..10 {
+        xy.0[i] = x.0[i] + y.0[i];
+    }
+    return xy;
+}
+
+pub fn sub(x: &FieldElement, y: &FieldElement) -> FieldElement {
+    let mut xy = FieldElement::ZERO;
+    for i in 0..10 {
+        xy.0[i] = x.0[i] - y.0[i];
+    }
+    return xy;
+}
+
+/// Swaps `a` and `b` if `swap` is 1 and leaves them if it is 0, without branching on `swap`.
+pub fn cswap(a: &mut FieldElement, b: &mut FieldElement, swap: i64) {
+    let mask = -swap;
+    for i in 0..10 {
+        let t = mask & (a.0[i] ^ b.0[i]);
+        a.0[i] ^= t;
+        b.0[i] ^= t;
+    }
+}
+
+/// Multiplies by a small integer in the range -185861411 to 185861411. `x` need not be reduced.
+pub fn mul_small(x: &FieldElement, y: i64) -> FieldElement {
+    let x = &x.0;
+    let mut xy = [0i64; 10];
+    let mut t = x[8] * y;
+    xy[8] = t & P26;
+    t = (t >> 26) + x[9] * y;
+    xy[9] = t & P25;
+    t = 19 * (t >> 25) + x[0] * y;
+    xy[0] = t & P26;
+    t = (t >> 26) + x[1] * y;
+    xy[1] = t & P25;
+    t = (t >> 25) + x[2] * y;
+    xy[2] = t & P26;
+    t = (t >> 26) + x[3] * y;
+    xy[3] = t & P25;
+    t = (t >> 25) + x[4] * y;
+    xy[4] = t & P26;
+    t = (t >> 26) + x[5] * y;
+    xy[5] = t & P25;
+    t = (t >> 25) + x[6] * y;
+    xy[6] = t & P26;
+    t = (t >> 26) + x[7] * y;
+    xy[7] = t & P25;
+    t = (t >> 25) + xy[8];
+    xy[8] = t & P26;
+    xy[9] += t >> 26;
+    return FieldElement(xy);
+}
+
+/// Multiplies two numbers, which need not be reduced.
+pub fn mul(x: &FieldElement, y: &FieldElement) -> FieldElement {
+    let [x0, x1, x2, x3, x4, x5, x6, x7, x8, x9] = x.0;
+    let [y0, y1, y2, y3, y4, y5, y6, y7, y8, y9] = y.0;
+    let mut xy = [0i64; 10];
+    let mut t = (x0 * y8) + (x2 * y6) + (x4 * y4) + (x6 * y2) + (x8 * y0) + 2 * ((x1 * y7) + (x3 * y5) + (x5 * y3) + (x7 * y1)) + 38 * (x9 * y9);
+    xy[8] = t & P26;
+    t = (t >> 26) + (x0 * y9) + (x1 * y8) + (x2 * y7) + (x3 * y6) + (x4 * y5) + (x5 * y4) + (x6 * y3) + (x7 * y2) + (x8 * y1) + (x9 * y0);
+    xy[9] = t & P25;
+    t = (x0 * y0) + 19 * ((t >> 25) + (x2 * y8) + (x4 * y6) + (x6 * y4) + (x8 * y2)) + 38 * ((x1 * y9) + (x3 * y7) + (x5 * y5) + (x7 * y3) + (x9 * y1));
+    xy[0] = t & P26;
+    t = (t >> 26) + (x0 * y1) + (x1 * y0) + 19 * ((x2 * y9) + (x3 * y8) + (x4 * y7) + (x5 * y6) + (x6 * y5) + (x7 * y4) + (x8 * y3) + (x9 * y2));
+    xy[1] = t & P25;
+    t = (t >> 25) + (x0 * y2) + (x2 * y0) + 19 * ((x4 * y8) + (x6 * y6) + (x8 * y4)) + 2 * (x1 * y1) + 38 * ((x3 * y9) + (x5 * y7) + (x7 * y5) + (x9 * y3));
+    xy[2] = t & P26;
+    t = (t >> 26) + (x0 * y3) + (x1 * y2) + (x2 * y1) + (x3 * y0) + 19 * ((x4 * y9) + (x5 * y8) + (x6 * y7) + (x7 * y6) + (x8 * y5) + (x9 * y4));
+    xy[3] = t & P25;
+    t = (t >> 25) + (x0 * y4) + (x2 * y2) + (x4 * y0) + 19 * ((x6 * y8) + (x8 * y6)) + 2 * ((x1 * y3) + (x3 * y1)) + 38 * ((x5 * y9) + (x7 * y7) + (x9 * y5));
+    xy[4] = t & P26;
+    t = (t >> 26) + (x0 * y5) + (x1 * y4) + (x2 * y3) + (x3 * y2) + (x4 * y1) + (x5 * y0) + 19 * ((x6 * y9) + (x7 * y8) + (x8 * y7) + (x9 * y6));
+    xy[5] = t & P25;
+    t = (t >> 25) + (x0 * y6) + (x2 * y4) + (x4 * y2) + (x6 * y0) + 19 * (x8 * y8) + 2 * ((x1 * y5) + (x3 * y3) + (x5 * y1)) + 38 * ((x7 * y9) + (x9 * y7));
+    xy[6] = t & P26;
+    t = (t >> 26) + (x0 * y7) + (x1 * y6) + (x2 * y5) + (x3 * y4) + (x4 * y3) + (x5 * y2) + (x6 * y1) + (x7 * y0) + 19 * ((x8 * y9) + (x9 * y8));
+    xy[7] = t & P25;
+    t = (t >> 25) + xy[8];
+    xy[8] = t & P26;
+    xy[9] += t >> 26;
+    return FieldElement(xy);
+}
+
+/// Squares a number, which need not be reduced.
+pub fn sqr(x: &FieldElement) -> FieldElement {
+    let [x0, x1, x2, x3, x4, x5, x6, x7, x8, x9] = x.0;
+    let mut square = [0i64; 10];
+    let mut t = (x4 * x4) + 2 * ((x0 * x8) + (x2 * x6)) + 38 * (x9 * x9) + 4 * ((x1 * x7) + (x3 * x5));
+    square[8] = t & P26;
+    t = (t >> 26) + 2 * ((x0 * x9) + (x1 * x8) + (x2 * x7) + (x3 * x6) + (x4 * x5));
+    square[9] = t & P25;
+    t = 19 * (t >> 25) + (x0 * x0) + 38 * ((x2 * x8) + (x4 * x6) + (x5 * x5)) + 76 * ((x1 * x9) + (x3 * x7));
+    square[0] = t & P26;
+    t = (t >> 26) + 2 * (x0 * x1) + 38 * ((x2 * x9) + (x3 * x8) + (x4 * x7) + (x5 * x6));
+    square[1] = t & P25;
+    t = (t >> 25) + 19 * (x6 * x6) + 2 * ((x0 * x2) + (x1 * x1)) + 38 * (x4 * x8) + 76 * ((x3 * x9) + (x5 * x7));
+    square[2] = t & P26;
+    t = (t >> 26) + 2 * ((x0 * x3) + (x1 * x2)) + 38 * ((x4 * x9) + (x5 * x8) + (x6 * x7));
+    square[3] = t & P25;
+    t = (t >> 25) + (x2 * x2) + 2 * (x0 * x4) + 38 * ((x6 * x8) + (x7 * x7)) + 4 * (x1 * x3) + 76 * (x5 * x9);
+    square[4] = t & P26;
+    t = (t >> 26) + 2 * ((x0 * x5) + (x1 * x4) + (x2 * x3)) + 38 * ((x6 * x9) + (x7 * x8));
+    square[5] = t & P25;
+    t = (t >> 25) + 19 * (x8 * x8) + 2 * ((x0 * x6) + (x2 * x4) + (x3 * x3)) + 4 * (x1 * x5) + 76 * (x7 * x9);
+    square[6] = t & P26;
+    t = (t >> 26) + 2 * ((x0 * x7) + (x1 * x6) + (x2 * x5) + (x3 * x4)) + 38 * (x8 * x9);
+    square[7] = t & P25;
+    t = (t >> 25) + square[8];
+    square[8] = t & P26;
+    square[9] += t >> 26;
+    return FieldElement(square);
+}
+
+/// x^(p - 2), the reciprocal of `x`, or with `sqrt_assist` x^((p - 5) / 8) for `sqrt`.
+pub fn recip(x: &FieldElement, sqrt_assist: bool) -> FieldElement {
+    // the chain for x^(2^255 - 21) is straight from djb's implementation
+    let t1 = sqr(x); // 2 == 2 * 1
+    let t2 = sqr(&t1); // 4 == 2 * 2
+    let t0 = sqr(&t2); // 8 == 2 * 4
+    let t2 = mul(&t0, x); // 9 == 8 + 1
+    let t0 = mul(&t2, &t1); // 11 == 9 + 2
+    let t1 = sqr(&t0); // 22 == 2 * 11
+    let t3 = mul(&t1, &t2); // 31 == 22 + 9 == 2^5 - 2^0
+    let t1 = square_times(&t3, 5); // 2^10 - 2^5
+    let t2 = mul(&t1, &t3); // 2^10 - 2^0
+    let t3 = square_times(&t2, 10); // 2^20 - 2^10
+    let t1 = mul(&t3, &t2); // 2^20 - 2^0
+    let t4 = square_times(&t1, 20); // 2^40 - 2^20
+    let t3 = mul(&t4, &t1); // 2^40 - 2^0
+    let t3 = square_times(&t3, 10); // 2^50 - 2^10
+    let t1 = mul(&t3, &t2); // 2^50 - 2^0
+    let t3 = square_times(&t1, 50); // 2^100 - 2^50
+    let t2 = mul(&t3, &t1); // 2^100 - 2^0
+    let t4 = square_times(&t2, 100); // 2^200 - 2^100
+    let t3 = mul(&t4, &t2); // 2^200 - 2^0
+    let t3 = square_times(&t3, 50); // 2^250 - 2^50
+    let t2 = mul(&t3, &t1); // 2^250 - 2^0
+    let t2 = square_times(&t2, 2); // 2^252 - 2^2
+    if sqrt_assist {
+        return mul(x, &t2); // 2^252 - 3
+    }
+    let t1 = square_times(&t2, 3); // 2^255 - 2^5
+    return mul(&t1, &t0); // 2^255 - 21
+}
+
+/// A square root of `u`, if it has one.
+pub fn sqrt(u: &FieldElement) -> FieldElement {
+    let t1 = add(u, u); // t1 = 2u
+    let v = recip(&t1, true); // v = (2u)^((p - 5) / 8)
+    let x = sqr(&v); // x = v^2
+    let mut t2 = mul(&t1, &x); // t2 = 2uv^2
+    t2.0[0] -= 1; // t2 = 2uv^2 - 1
+    let t1 = mul(&v, &t2); // t1 = v(2uv^2 - 1)
+    return mul(u, &t1); // x = uv(2uv^2 - 1)
+}
+
+fn square_times(x: &FieldElement, times: usize) -> FieldElement {
+    let mut result = sqr(x);
+    for _ in 1..times {
+        result = sqr(&result);
+    }
+    return result;
+}
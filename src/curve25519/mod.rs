@@ -0,0 +1,323 @@
+//! EC-KCDSA signatures and key agreement over Curve25519, as used by Burst.
+//!
+//! The primitives are in `primitives`, a port of the C implementation the scheme was specified against,
+//! which also notes which paths are constant time.
+
+mod field;
+mod primitives;
+mod scalar;
+
+use sha2::{Sha256, Digest};
+use std::thread;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+pub use self::primitives::clamp;
+
+pub fn get_public_key(private_key: &[u8], public_key_buffer: &mut [u8]) {
+    let mut k = copy_secret(private_key);
+    let mut public_key = [0u8; 32];
+    primitives::keygen(&mut public_key, None, &mut k);
+    public_key_buffer[..32].copy_from_slice(&public_key);
+}
+
+/// The private key is clamped first, as it is for key generation, so a key gives the same secret
+/// whether or not it was clamped beforehand.
+pub fn get_shared_secret(private_key: &[u8], public_key: &[u8], shared_secret_buffer: &mut [u8]) {
+    let mut k = copy_secret(private_key);
+    clamp(&mut k);
+    let mut shared_secret = Zeroizing::new([0u8; 32]);
+    primitives::curve(&mut shared_secret, &k, &array32(public_key));
+    shared_secret_buffer[..32].copy_from_slice(&shared_secret[..]);
+}
+
+pub fn sign(private_key: &[u8], message_sha256: &[u8], signature_buffer: &mut [u8]) {
+    let mut signing_key = Zeroizing::new([0u8; 32]);
+    get_signing_key(private_key, &mut signing_key[..]);
+    sign_with_signing_key(&signing_key[..], message_sha256, signature_buffer);
+}
+
+/// The EC-KCDSA signing key s of a private key, which is all `sign_with_signing_key` needs.
+///
+/// The private key is clamped first, as it is for key generation.
+pub fn get_signing_key(private_key: &[u8], signing_key_buffer: &mut [u8]) {
+    let mut k = copy_secret(private_key);
+    let mut public_key = [0u8; 32];
+    let mut signing_key = Zeroizing::new([0u8; 32]);
+    primitives::keygen(&mut public_key, Some(&mut signing_key), &mut k);
+    signing_key_buffer[..32].copy_from_slice(&signing_key[..]);
+}
+
+/// Signs with the signing key from `get_signing_key`, giving the same signature as `sign` with the private key.
+pub fn sign_with_signing_key(signing_key: &[u8], message_sha256: &[u8], signature_buffer: &mut [u8]) {
+    let signing_key = copy_secret(signing_key);
+    let mut x = hash_secret(message_sha256, &signing_key);
+
+    // keygen clamps x, which is then used clamped for the signature
+    let mut y = Zeroizing::new([0u8; 32]);
+    primitives::keygen(&mut y, None, &mut x);
+
+    let mut sha256 = Sha256::new();
+    sha256.input(message_sha256);
+    sha256.input(&y[..]);
+    let h = array32(&sha256.result());
+
+    let mut v = [0u8; 32];
+    primitives::sign(&mut v, &h, &x, &signing_key);
+    signature_buffer[..32].copy_from_slice(&v);
+    signature_buffer[32..64].copy_from_slice(&h);
+}
+
+pub fn verify(public_key: &[u8], signature: &[u8], message_sha256: &[u8], enforce_canonical: bool) -> bool {
+    if enforce_canonical {
+        if !is_canonical_public_key(public_key) { return false; }
+        if !is_canonical_signature(signature) { return false; }
+    }
+
+    let mut y = [0u8; 32];
+    let h = array32(&signature[32..]);
+    primitives::verify(&mut y, &array32(signature), &h, &array32(public_key));
+
+    let mut sha256 = Sha256::new();
+    sha256.input(message_sha256);
+    sha256.input(y);
+    let h2 = sha256.result();
+
+    return eq(&h, &h2);
+}
+
+/// Fewest signatures worth handing to a thread of their own.
+const MIN_BATCH_PER_THREAD: usize = 16;
+
+/// Verifies many signatures across the available cores, writing whether each one is valid to `results`.
+///
+/// All four slices must have the same length. Item `i` is `verify(&public_keys[i], &signatures[i],
+/// &message_hashes[i], enforce_canonical)`.
+pub fn verify_batch(public_keys: &[[u8; 32]], signatures: &[[u8; 64]], message_hashes: &[[u8; 32]], enforce_canonical: bool, results: &mut [bool]) {
+    let count = results.len();
+    assert!(public_keys.len() == count && signatures.len() == count && message_hashes.len() == count, "batch slices differ in length");
+
+    let verify_range = |start: usize, results: &mut [bool]| {
+        for (i, result) in results.iter_mut().enumerate() {
+            *result = verify(&public_keys[start + i], &signatures[start + i], &message_hashes[start + i], enforce_canonical);
+        }
+    };
+
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get()).min(count / MIN_BATCH_PER_THREAD);
+    if threads <= 1 {
+        verify_range(0, results);
+        return;
+    }
+    let chunk_size = count.div_ceil(threads);
+    thread::scope(|scope| {
+        for (i, chunk) in results.chunks_mut(chunk_size).enumerate() {
+            let verify_range = &verify_range;
+            scope.spawn(move || verify_range(i * chunk_size, chunk));
+        }
+    });
+}
+
+/// Copies a secret key so clamping it, as `primitives::keygen` does, never touches the caller's.
+/// The copy is wiped when dropped.
+fn copy_secret(key: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut copy = Zeroizing::new([0u8; 32]);
+    copy.copy_from_slice(&key[..32]);
+    return copy;
+}
+
+/// SHA-256 of `message_sha256` followed by `secret`, without leaving either the input or the result behind.
+fn hash_secret(message_sha256: &[u8], secret: &[u8; 32]) -> Zeroizing<[u8; 32]> {
+    let mut input = Zeroizing::new([0u8; 64]);
+    input[..32].copy_from_slice(&message_sha256[..32]);
+    input[32..].copy_from_slice(secret);
+    let mut hash = Sha256::digest(&input[..]);
+    let mut result = Zeroizing::new([0u8; 32]);
+    result.copy_from_slice(&hash);
+    hash.as_mut_slice().zeroize();
+    return result;
+}
+
+pub fn is_canonical_public_key(public_key: &[u8]) -> bool {
+    return primitives::is_canonical_public_key(&array32(public_key));
+}
+
+pub fn is_canonical_signature(signature: &[u8]) -> bool {
+    return primitives::is_canonical_signature(&array32(signature));
+}
+
+/// Copies `signature` into `canonical_buffer` with its first half reduced modulo the group order.
+///
+/// Verifies exactly as the original for public keys generated by `keygen`.
+pub fn canonicalize_signature(signature: &[u8], canonical_buffer: &mut [u8]) {
+    let mut v = array32(signature);
+    primitives::canonicalize_signature(&mut v);
+    canonical_buffer[..32].copy_from_slice(&v);
+    canonical_buffer[32..64].copy_from_slice(&signature[32..64]);
+}
+
+/// Copies `public_key` into `canonical_buffer` reduced modulo 2^255 - 19.
+///
+/// Verifies exactly as the original, but hashes to a different account id.
+pub fn canonicalize_public_key(public_key: &[u8], canonical_buffer: &mut [u8]) {
+    let mut canonical = array32(public_key);
+    primitives::canonicalize_public_key(&mut canonical);
+    canonical_buffer[..32].copy_from_slice(&canonical);
+}
+
+fn array32(bytes: &[u8]) -> [u8; 32] {
+    let mut array = [0u8; 32];
+    array.copy_from_slice(&bytes[..32]);
+    return array;
+}
+
+/// Compares in constant time, so how long verification takes says nothing about how close a forgery came.
+fn eq(slice1: &[u8], slice2: &[u8]) -> bool {
+    return slice1.ct_eq(slice2).into();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Instant;
+
+    /// dudect style timing test: times `f` on inputs of two classes, a fixed input and random ones,
+    /// interleaved at random, and returns Welch's t statistic of the two timing distributions.
+    ///
+    /// The slowest 10% of measurements are cropped to drop interrupts and the like. |t| above 4.5 is
+    /// taken as evidence of a timing difference between the classes, above 10 as near certain.
+    fn timing_t<F: FnMut(&[u8; 32])>(fixed: [u8; 32], samples: usize, mut f: F) -> f64 {
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut inputs = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            let class = next() & 1 == 0;
+            let mut input = fixed;
+            if !class {
+                for chunk in input.chunks_exact_mut(8) {
+                    chunk.copy_from_slice(&next().to_le_bytes());
+                }
+            }
+            inputs.push((class, input));
+        }
+
+        let mut timings: Vec<(bool, f64)> = inputs.iter().map(|(class, input)| {
+            let start = Instant::now();
+            f(input);
+            (*class, start.elapsed().as_nanos() as f64)
+        }).collect();
+        timings.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        timings.truncate(samples * 9 / 10);
+
+        let stats = |class: bool| {
+            let values: Vec<f64> = timings.iter().filter(|t| t.0 == class).map(|t| t.1).collect();
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1.0);
+            (n, mean, variance)
+        };
+        let (n0, mean0, variance0) = stats(true);
+        let (n1, mean1, variance1) = stats(false);
+        return (mean0 - mean1) / (variance0 / n0 + variance1 / n1).sqrt();
+    }
+
+    #[test]
+    fn shared_secret_clamps_private_key() {
+        let private_key = [0xFFu8; 32];
+        let mut clamped_key = private_key;
+        clamp(&mut clamped_key);
+        let mut other_public_key = [0u8; 32];
+        get_public_key(&[0x42; 32], &mut other_public_key);
+
+        let mut shared_secret = [0u8; 32];
+        get_shared_secret(&private_key, &other_public_key, &mut shared_secret);
+        let mut clamped_shared_secret = [0u8; 32];
+        get_shared_secret(&clamped_key, &other_public_key, &mut clamped_shared_secret);
+        assert_eq!(shared_secret, clamped_shared_secret);
+
+        // and the other party arrives at the same secret
+        let mut public_key = [0u8; 32];
+        get_public_key(&private_key, &mut public_key);
+        let mut other_shared_secret = [0u8; 32];
+        get_shared_secret(&[0x42; 32], &public_key, &mut other_shared_secret);
+        assert_eq!(shared_secret, other_shared_secret);
+    }
+
+    #[test]
+    fn verify_batch_matches_verify() {
+        let count = 100;
+        let mut public_keys = vec![[0u8; 32]; count];
+        let mut signatures = vec![[0u8; 64]; count];
+        let mut message_hashes = vec![[0u8; 32]; count];
+        for i in 0..count {
+            let private_key = [i as u8; 32];
+            get_public_key(&private_key, &mut public_keys[i]);
+            message_hashes[i] = [(i * 7) as u8; 32];
+            sign(&private_key, &message_hashes[i], &mut signatures[i]);
+        }
+        signatures[3][40] ^= 1;
+        message_hashes[50][0] ^= 1;
+        public_keys[99] = public_keys[98];
+
+        let mut results = vec![false; count];
+        verify_batch(&public_keys, &signatures, &message_hashes, true, &mut results);
+        for (i, &result) in results.iter().enumerate() {
+            assert_eq!(result, ![3, 50, 99].contains(&i), "signature {}", i);
+        }
+
+        // too few to be worth threads
+        let mut results = [false; 4];
+        verify_batch(&public_keys[1..5], &signatures[1..5], &message_hashes[1..5], true, &mut results);
+        assert_eq!(results, [true, true, false, true]);
+    }
+
+    // The timing tests are noisy on a loaded machine, run them on their own with
+    // `cargo test --release -- --ignored --nocapture timing_`.
+
+    #[test]
+    #[ignore]
+    fn timing_keygen() {
+        let mut public_key = [0u8; 32];
+        let t = timing_t([0x55; 32], 20_000, |private_key| get_public_key(private_key, &mut public_key));
+        println!("keygen t = {:.2}", t);
+        assert!(t.abs() < 10.0, "keygen timing depends on the private key, t = {:.2}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn timing_shared_secret() {
+        let mut public_key = [0u8; 32];
+        get_public_key(&[0x42; 32], &mut public_key);
+        let mut shared_secret = [0u8; 32];
+        let t = timing_t([0x55; 32], 20_000, |private_key| get_shared_secret(private_key, &public_key, &mut shared_secret));
+        println!("shared secret t = {:.2}", t);
+        assert!(t.abs() < 10.0, "key agreement timing depends on the private key, t = {:.2}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn timing_sign() {
+        // the message hash determines the per-signature key x, the signing key derivation is
+        // not constant time (see the notes in primitives.rs) but the same for every message
+        let private_key = [0x42u8; 32];
+        let mut signature = [0u8; 64];
+        let t = timing_t([0x55; 32], 20_000, |message_sha256| sign(&private_key, message_sha256, &mut signature));
+        println!("sign t = {:.2}", t);
+        assert!(t.abs() < 10.0, "signing time depends on the message, t = {:.2}", t);
+    }
+
+    #[test]
+    #[ignore]
+    fn timing_hash_comparison() {
+        let expected = [0x55u8; 32];
+        let t = timing_t(expected, 1_000_000, |h| {
+            std::hint::black_box(eq(&expected, std::hint::black_box(h)));
+        });
+        println!("comparison t = {:.2}", t);
+        assert!(t.abs() < 10.0, "comparison time depends on how many bytes match, t = {:.2}", t);
+    }
+}
@@ -0,0 +1,104 @@
+//! Little endian byte string arithmetic, used for scalars modulo the group order.
+//!
+//! Numbers are plain `[u8]` slices, least significant byte first. The functions mirror the radix 2^8
+//! helpers of the original C implementation, including their carry conventions, so results are identical
+//! to the last bit - `egcd32` in particular returns its inverse as a signed 32 byte number.
+
+/// The group order, a prime near 2^252 + 2^124.
+pub const ORDER: [u8; 32] = [
+    237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+];
+
+/// The smallest multiple of the group order that is at least 2^255.
+pub const ORDER_TIMES_8: [u8; 32] = [
+    104, 159, 174, 231, 210, 24, 147, 192, 178, 230, 188, 23, 245, 206, 247, 166, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128,
+];
+
+/// `p[m..m + n] += z * x[..n]`, returning the carry out of `p[m + n - 1]`.
+pub fn mula_small(p: &mut [u8], m: usize, x: &[u8], n: usize, z: i32) -> i32 {
+    let mut v = 0i32;
+    for i in 0..n {
+        v += p[i + m] as i32 + z * x[i] as i32;
+        p[i + m] = v as u8;
+        v >>= 8;
+    }
+    return v;
+}
+
+/// `p += x * y[..t] * z` where `x` is 32 bytes, `p` is `32 + t` bytes and `z` is a small integer.
+pub fn mula32(p: &mut [u8], x: &[u8], y: &[u8], t: usize, z: i32) -> i32 {
+    let n = 31;
+    let mut w = 0i32;
+    for i in 0..t {
+        let zy = z * y[i] as i32;
+        w += mula_small(p, i, x, n, zy) + p[i + n] as i32 + zy * x[n] as i32;
+        p[i + n] = w as u8;
+        w >>= 8;
+    }
+    p[t + n] = (w + p[t + n] as i32) as u8;
+    return w >> 8;
+}
+
+/// Divides `r[..n]` by `d[..t]`, leaving the `n - t + 1` byte quotient in `q` and the `t` byte remainder in `r`.
+///
+/// Requires `t > 0` and `d[t - 1] != 0`.
+pub fn divmod(q: &mut [u8], r: &mut [u8], n: usize, d: &[u8], t: usize) {
+    let mut rn = 0i32;
+    let mut dt = (d[t - 1] as i32) << 8;
+    if t > 1 { dt |= d[t - 2] as i32; }
+    let mut n = n;
+    while n >= t {
+        n -= 1;
+        let mut z = (rn << 16) | ((r[n] as i32) << 8);
+        if n > 0 { z |= r[n - 1] as i32; }
+        z /= dt;
+        rn += mula_small(r, n + 1 - t, d, t, -z);
+        // rn is 0 or -1 (underflow)
+        q[n + 1 - t] = (z + rn) as u8;
+        mula_small(r, n + 1 - t, d, t, -rn);
+        rn = r[n] as i32;
+        r[n] = 0;
+    }
+    r[t - 1] = rn as u8;
+}
+
+/// The length of `x[..n]` without its leading (most significant) zero bytes.
+pub fn numsize(x: &[u8], n: usize) -> usize {
+    let mut n = n;
+    while n != 0 && x[n - 1] == 0 {
+        n -= 1;
+    }
+    return n;
+}
+
+/// The inverse of `a` modulo `b` by the extended Euclidean algorithm, as a signed 32 byte number.
+///
+/// Both `a` and `b` are used as scratch space. Not constant time: the number of steps depends on `a`.
+pub fn egcd32(a: &mut [u8; 32], b: &mut [u8; 32]) -> [u8; 32] {
+    let mut x = [0u8; 64];
+    let mut y = [0u8; 64];
+    x[0] = 1;
+    let mut an = numsize(a, 32);
+    let mut bn = 32;
+    let mut temp = [0u8; 32];
+    let first_32 = |buffer: &[u8; 64]| {
+        let mut result = [0u8; 32];
+        result.copy_from_slice(&buffer[..32]);
+        result
+    };
+    // division by zero
+    if an == 0 { return first_32(&y); }
+    loop {
+        let qn = bn - an + 1;
+        divmod(&mut temp, b, bn, a, an);
+        bn = numsize(b, bn);
+        if bn == 0 { return first_32(&x); }
+        mula32(&mut y, &x, &temp, qn, -1);
+
+        let qn = an - bn + 1;
+        divmod(&mut temp, a, an, b, bn);
+        an = numsize(a, an);
+        if an == 0 { return first_32(&y); }
+        mula32(&mut x, &y, &temp, qn, -1);
+    }
+}
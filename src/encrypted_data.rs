@@ -0,0 +1,215 @@
+//! Burst encrypted messages (`EncryptedData`), as attached to transactions by the reference wallet.
+//!
+//! The plaintext is gzipped and encrypted with AES-256-CBC (PKCS#7 padding, random IV prepended to the
+//! ciphertext). The AES key is SHA-256 of the Curve25519 shared secret of the two accounts XORed with a
+//! random 32 byte nonce, which is sent along with the data. Either party can decrypt with their private key
+//! and the other's public key, and messages encrypted "to self" use the sender's own public key.
+
+use crate::keys::PrivateKey;
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{Read, Write};
+use zeroize::{Zeroize, Zeroizing};
+
+type Aes256CbcEncryptor = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDecryptor = cbc::Decryptor<aes::Aes256>;
+
+const IV_SIZE: usize = 16;
+
+/// An encrypted message and the nonce needed, together with the shared secret, to decrypt it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedData {
+    /// The AES IV followed by the ciphertext.
+    pub data: Vec<u8>,
+    pub nonce: [u8; 32],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecryptionError {
+    /// The data is not an IV followed by whole AES blocks.
+    InvalidLength,
+    /// The padding is wrong, which usually means the key is wrong.
+    InvalidPadding,
+    /// The decrypted data is not gzipped.
+    InvalidCompression,
+}
+
+impl fmt::Display for DecryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            DecryptionError::InvalidLength => write!(f, "encrypted data is an IV followed by whole AES blocks"),
+            DecryptionError::InvalidPadding => write!(f, "invalid padding, the keys or nonce are probably wrong"),
+            DecryptionError::InvalidCompression => write!(f, "the decrypted data is not gzipped"),
+        };
+    }
+}
+
+impl std::error::Error for DecryptionError {}
+
+/// Encrypts `plaintext` from the owner of `my_private_key` to the owner of `their_public_key`.
+///
+/// An empty plaintext gives empty data with an all zero nonce, which the reference wallet sends as nothing at all.
+pub fn encrypt_data(plaintext: &[u8], my_private_key: &PrivateKey, their_public_key: &[u8; 32]) -> EncryptedData {
+    if plaintext.is_empty() {
+        return EncryptedData { data: Vec::new(), nonce: [0u8; 32] };
+    }
+    let mut nonce = [0u8; 32];
+    let mut iv = [0u8; IV_SIZE];
+    fill_random(&mut nonce);
+    fill_random(&mut iv);
+    let data = encrypt_with(plaintext, my_private_key, their_public_key, &nonce, &iv);
+    return EncryptedData { data, nonce };
+}
+
+/// Decrypts data encrypted by `encrypt_data` between the owner of `my_private_key` and the owner of `their_public_key`,
+/// whichever of them was the sender.
+pub fn decrypt_data(encrypted: &EncryptedData, my_private_key: &PrivateKey, their_public_key: &[u8; 32]) -> Result<Vec<u8>, DecryptionError> {
+    if encrypted.data.is_empty() {
+        return Ok(Vec::new());
+    }
+    if encrypted.data.len() < 2 * IV_SIZE || !encrypted.data.len().is_multiple_of(IV_SIZE) {
+        return Err(DecryptionError::InvalidLength);
+    }
+
+    let key = aes_key(my_private_key, their_public_key, &encrypted.nonce);
+    let (iv, ciphertext) = encrypted.data.split_at(IV_SIZE);
+    let compressed = Zeroizing::new(
+        Aes256CbcDecryptor::new(key.as_ref().into(), iv.into())
+            .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+            .map_err(|_| DecryptionError::InvalidPadding)?,
+    );
+
+    let mut plaintext = Vec::new();
+    if MultiGzDecoder::new(&compressed[..]).read_to_end(&mut plaintext).is_err() {
+        plaintext.zeroize();
+        return Err(DecryptionError::InvalidCompression);
+    }
+    return Ok(plaintext);
+}
+
+/// Encrypts `plaintext` so that only the owner of `my_private_key` can read it.
+pub fn encrypt_to_self(plaintext: &[u8], my_private_key: &PrivateKey) -> EncryptedData {
+    return encrypt_data(plaintext, my_private_key, &my_private_key.public_key());
+}
+
+/// Decrypts data encrypted by `encrypt_to_self`.
+pub fn decrypt_from_self(encrypted: &EncryptedData, my_private_key: &PrivateKey) -> Result<Vec<u8>, DecryptionError> {
+    return decrypt_data(encrypted, my_private_key, &my_private_key.public_key());
+}
+
+fn encrypt_with(plaintext: &[u8], my_private_key: &PrivateKey, their_public_key: &[u8; 32], nonce: &[u8; 32], iv: &[u8; IV_SIZE]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(plaintext).expect("writing to a Vec cannot fail");
+    let compressed = Zeroizing::new(encoder.finish().expect("writing to a Vec cannot fail"));
+
+    let key = aes_key(my_private_key, their_public_key, nonce);
+    let mut data = iv.to_vec();
+    data.extend(Aes256CbcEncryptor::new(key.as_ref().into(), iv.into()).encrypt_padded_vec_mut::<Pkcs7>(&compressed));
+    return data;
+}
+
+/// SHA-256 of the shared secret XORed with `nonce`.
+fn aes_key(my_private_key: &PrivateKey, their_public_key: &[u8; 32], nonce: &[u8; 32]) -> Zeroizing<[u8; 32]> {
+    let shared_secret = my_private_key.shared_secret(their_public_key);
+    let mut input = Zeroizing::new(*shared_secret.as_bytes());
+    for (byte, nonce_byte) in input.iter_mut().zip(nonce.iter()) {
+        *byte ^= nonce_byte;
+    }
+    let mut hash = Sha256::digest(&input[..]);
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&hash);
+    hash.as_mut_slice().zeroize();
+    return key;
+}
+
+fn fill_random(buffer: &mut [u8]) {
+    getrandom::getrandom(buffer).expect("the system random number generator failed");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encrypted_data_round_trip() {
+        let alice = PrivateKey::from_bytes(&[0x11; 32]);
+        let bob = PrivateKey::from_bytes(&[0x22; 32]);
+        let eve = PrivateKey::from_bytes(&[0x33; 32]);
+        let plaintext = b"Meet me at block 500000, bring the plots.".repeat(3);
+
+        let encrypted = encrypt_data(&plaintext, &alice, &bob.public_key());
+        assert_eq!(encrypted.data.len() % 16, 0);
+        assert_eq!(decrypt_data(&encrypted, &bob, &alice.public_key()).unwrap(), plaintext);
+        assert_eq!(decrypt_data(&encrypted, &alice, &bob.public_key()).unwrap(), plaintext);
+        assert!(decrypt_data(&encrypted, &eve, &alice.public_key()).is_err());
+
+        // fresh nonce and IV every time
+        assert_ne!(encrypt_data(&plaintext, &alice, &bob.public_key()), encrypted);
+
+        let to_self = encrypt_to_self(&plaintext, &alice);
+        assert_eq!(decrypt_from_self(&to_self, &alice).unwrap(), plaintext);
+        assert!(decrypt_from_self(&to_self, &bob).is_err());
+
+        let empty = encrypt_data(b"", &alice, &bob.public_key());
+        assert!(empty.data.is_empty());
+        assert_eq!(decrypt_data(&empty, &bob, &alice.public_key()).unwrap(), b"");
+    }
+
+    #[test]
+    fn encrypted_data_layout() {
+        let alice = PrivateKey::from_bytes(&[0x11; 32]);
+        let bob = PrivateKey::from_bytes(&[0x22; 32]);
+        let nonce = [0x5A; 32];
+        let iv = [0xA5; 16];
+        let data = encrypt_with(b"hello", &alice, &bob.public_key(), &nonce, &iv);
+        assert_eq!(data[..16], iv);
+
+        // decrypting by hand with SHA-256(shared secret ^ nonce) gives the gzipped plaintext
+        let mut input = *alice.shared_secret(&bob.public_key()).as_bytes();
+        for byte in input.iter_mut() {
+            *byte ^= 0x5A;
+        }
+        let key = Sha256::digest(&input);
+        let compressed = Aes256CbcDecryptor::new(key.as_slice().into(), (&iv).into()).decrypt_padded_vec_mut::<Pkcs7>(&data[16..]).unwrap();
+        assert_eq!(compressed[..2], [0x1F, 0x8B]);
+        let mut plaintext = Vec::new();
+        MultiGzDecoder::new(&compressed[..]).read_to_end(&mut plaintext).unwrap();
+        assert_eq!(plaintext, b"hello");
+
+        let mut encrypted = EncryptedData { data, nonce };
+        encrypted.data.truncate(24);
+        assert_eq!(decrypt_data(&encrypted, &bob, &alice.public_key()), Err(DecryptionError::InvalidLength));
+    }
+
+    #[test]
+    fn encrypted_data_known_answer() {
+        // Produced independently on the JVM the way the reference node does it (Crypto.getPrivateKey,
+        // Convert.compress and Crypto.aesEncrypt): the JDK's X25519 for the shared secret of the two clamped
+        // passphrase keys, GZIPOutputStream and AES/CBC/PKCS5Padding, with this nonce and IV
+        let alice = crate::account::keypair_from_passphrase(b"correct horse battery staple");
+        let bob = crate::account::keypair_from_passphrase(b"another passphrase");
+        assert_eq!(bob.public_key, [
+            91, 0, 106, 135, 134, 58, 109, 53, 164, 240, 88, 187, 52, 84, 22, 180, 93, 6, 200, 230, 183, 204, 54, 85, 236, 220, 24, 227, 246, 210, 20, 69,
+        ]);
+        let plaintext = b"Meet me at block 500000, bring the plots.";
+        let nonce = [
+            1, 8, 15, 22, 29, 36, 43, 50, 57, 64, 71, 78, 85, 92, 99, 106, 113, 120, 127, 134, 141, 148, 155, 162, 169, 176, 183, 190, 197, 204, 211, 218,
+        ];
+        let iv = [240, 237, 234, 231, 228, 225, 222, 219, 216, 213, 210, 207, 204, 201, 198, 195];
+        let data = vec![
+            240, 237, 234, 231, 228, 225, 222, 219, 216, 213, 210, 207, 204, 201, 198, 195, 150, 206, 244, 247, 26, 66, 153, 40, 226, 108, 187, 205,
+            147, 87, 126, 32, 147, 207, 170, 6, 211, 71, 119, 152, 195, 207, 88, 224, 215, 91, 5, 92, 190, 55, 29, 90, 131, 47, 53, 47, 148, 37, 196,
+            247, 217, 220, 145, 208, 6, 23, 87, 61, 28, 154, 52, 186, 209, 24, 96, 35, 0, 19, 114, 250,
+        ];
+
+        let encrypted = EncryptedData { data: data.clone(), nonce };
+        assert_eq!(decrypt_data(&encrypted, &bob.agreement_key, &alice.public_key).unwrap(), plaintext);
+        assert_eq!(decrypt_data(&encrypted, &alice.agreement_key, &bob.public_key).unwrap(), plaintext);
+        assert_eq!(encrypt_with(plaintext, &alice.agreement_key, &bob.public_key, &nonce, &iv), data);
+    }
+}
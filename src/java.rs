@@ -0,0 +1,274 @@
+//! JNI bindings for the `burst.kit.crypto.LibShabal` Java class (`java/burst/kit/crypto/LibShabal.java`),
+//! built with the `jni` feature.
+//!
+//! These call straight into the library without going through JNA. Array lengths are checked, throwing
+//! `IllegalArgumentException` if they are wrong and `NullPointerException` for null arrays. A panic throws
+//! `RuntimeException` rather than unwinding into the JVM.
+//!
+//! `java/test.sh` builds the library with the feature and runs `LibShabalTest` against it.
+
+use crate::curve25519;
+use crate::keys::PrivateKey;
+use crate::pocc::plot::SCOOP_SIZE;
+use crate::shabal::Shabal256;
+use crate::status;
+use jni::errors::Error as JniError;
+use jni::objects::{JByteArray, JByteBuffer, JClass, JLongArray, ReleaseMode};
+use jni::sys::{jboolean, jbyteArray, jlong, JNI_FALSE, JNI_TRUE};
+use jni::JNIEnv;
+use sha2::Digest;
+use std::ptr::{null, null_mut};
+use zeroize::Zeroize;
+
+enum Error {
+    IllegalArgument(String),
+    Jni(JniError),
+    Panic,
+}
+
+impl From<JniError> for Error {
+    fn from(error: JniError) -> Self {
+        return Error::Jni(error);
+    }
+}
+
+/// Runs `body`, turning an error or panic into a Java exception and returning `fallback` instead.
+fn throwing<'local, T, F>(env: &mut JNIEnv<'local>, fallback: T, body: F) -> T
+where
+    F: FnOnce(&mut JNIEnv<'local>) -> Result<T, Error>,
+{
+    let error = match status::catch_panic(Err(Error::Panic), || body(env)) {
+        Ok(result) => return result,
+        Err(error) => error,
+    };
+    // a failed JNI call may already have thrown, in which case that exception is the one to report
+    if env.exception_check().unwrap_or(true) {
+        return fallback;
+    }
+    let (class, message) = exception(error);
+    let _ = env.throw_new(class, message);
+    return fallback;
+}
+
+/// The class and message of the Java exception to throw for `error`.
+fn exception(error: Error) -> (&'static str, String) {
+    return match error {
+        Error::IllegalArgument(message) => ("java/lang/IllegalArgumentException", message),
+        Error::Jni(JniError::NullPtr(_)) => ("java/lang/NullPointerException", "a required array was null".to_string()),
+        Error::Jni(error) => ("java/lang/RuntimeException", error.to_string()),
+        Error::Panic => ("java/lang/RuntimeException", "unexpected internal error in libshabal".to_string()),
+    };
+}
+
+fn check_length(name: &str, expected: usize, len: usize) -> Result<(), Error> {
+    if len != expected {
+        return Err(Error::IllegalArgument(format!("{} must be {} bytes, not {}", name, expected, len)));
+    }
+    return Ok(());
+}
+
+/// Reads a byte array which must be exactly `N` bytes long.
+fn read_array<const N: usize>(env: &JNIEnv, array: &JByteArray, name: &str) -> Result<[u8; N], Error> {
+    check_length(name, N, env.get_array_length(array)? as usize)?;
+    let mut bytes = [0i8; N];
+    env.get_byte_array_region(array, 0, &mut bytes)?;
+    let mut result = [0u8; N];
+    for (byte, &signed) in result.iter_mut().zip(bytes.iter()) {
+        *byte = signed as u8;
+    }
+    bytes.zeroize();
+    return Ok(result);
+}
+
+fn read_private_key(env: &JNIEnv, array: &JByteArray) -> Result<PrivateKey, Error> {
+    let mut bytes = read_array::<32>(env, array, "privateKey")?;
+    let private_key = PrivateKey::from_bytes(&bytes);
+    bytes.zeroize();
+    return Ok(private_key);
+}
+
+fn check_nonce_count(nonce_count: jlong, len: usize) -> Result<u64, Error> {
+    let needed = (nonce_count as u64).checked_mul(SCOOP_SIZE as u64);
+    return match needed {
+        Some(needed) if nonce_count >= 0 && needed <= len as u64 => Ok(nonce_count as u64),
+        _ => Err(Error::IllegalArgument(format!("{} nonces do not fit in {} bytes of scoops", nonce_count, len))),
+    };
+}
+
+fn check_result_array(env: &JNIEnv, result: &JLongArray) -> Result<(), Error> {
+    if env.get_array_length(result)? < 2 {
+        return Err(Error::IllegalArgument("result must have room for the deadline and the offset".to_string()));
+    }
+    return Ok(());
+}
+
+fn write_deadline(env: &JNIEnv, result: &JLongArray, deadline: u64, offset: u64) -> Result<(), Error> {
+    env.set_long_array_region(result, 0, &[deadline as jlong, offset as jlong])?;
+    return Ok(());
+}
+
+/// `static native void findBestDeadlineDirect(ByteBuffer scoops, long nonceCount, byte[] gensig, long[] result)`
+///
+/// Like `shabal_findBestDeadlineDirect()` on the scoops in a direct `ByteBuffer`, storing the best deadline in
+/// `result[0]` and the offset of its nonce in `result[1]`. The deadline is unsigned.
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_findBestDeadlineDirect<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    scoops: JByteBuffer<'local>,
+    nonce_count: jlong,
+    gensig: JByteArray<'local>,
+    result: JLongArray<'local>,
+) {
+    throwing(&mut env, (), |env| {
+        let gensig = read_array::<32>(env, &gensig, "gensig")?;
+        check_result_array(env, &result)?;
+        let address = env.get_direct_buffer_address(&scoops).map_err(|error| match error {
+            JniError::NullPtr(_) if !scoops.is_null() => Error::IllegalArgument("scoops must be a direct ByteBuffer".to_string()),
+            error => Error::Jni(error),
+        })?;
+        let nonce_count = check_nonce_count(nonce_count, env.get_direct_buffer_capacity(&scoops)?)?;
+
+        let mut deadline = u64::MAX;
+        let mut offset = 0;
+        crate::find_best_deadline_dispatch(address, nonce_count, gensig.as_ptr(), &mut deadline, &mut offset, null());
+        return write_deadline(env, &result, deadline, offset);
+    });
+}
+
+/// `static native void findBestDeadline(byte[] scoops, long nonceCount, byte[] gensig, long[] result)`
+///
+/// `findBestDeadlineDirect` for scoops in a `byte[]`, which is accessed in place where the JVM allows it.
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_findBestDeadline<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    scoops: JByteArray<'local>,
+    nonce_count: jlong,
+    gensig: JByteArray<'local>,
+    result: JLongArray<'local>,
+) {
+    throwing(&mut env, (), |env| {
+        let gensig = read_array::<32>(env, &gensig, "gensig")?;
+        check_result_array(env, &result)?;
+        let nonce_count = check_nonce_count(nonce_count, env.get_array_length(&scoops)? as usize)?;
+
+        let mut deadline = u64::MAX;
+        let mut offset = 0;
+        {
+            // no other JNI calls are allowed until the elements are released
+            let elements = unsafe { env.get_array_elements_critical(&scoops, ReleaseMode::NoCopyBack)? };
+            crate::find_best_deadline_dispatch(elements.as_ptr() as *const u8, nonce_count, gensig.as_ptr(), &mut deadline, &mut offset, null());
+        }
+        return write_deadline(env, &result, deadline, offset);
+    });
+}
+
+/// `static native byte[] shabal256(byte[] data)`
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_shabal256<'local>(mut env: JNIEnv<'local>, _class: JClass<'local>, data: JByteArray<'local>) -> jbyteArray {
+    return throwing(&mut env, null_mut(), |env| {
+        let data = env.convert_byte_array(&data)?;
+        return Ok(env.byte_array_from_slice(&Shabal256::digest(&data))?.into_raw());
+    });
+}
+
+/// `static native byte[] getPublicKey(byte[] privateKey)`
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_getPublicKey<'local>(mut env: JNIEnv<'local>, _class: JClass<'local>, private_key: JByteArray<'local>) -> jbyteArray {
+    return throwing(&mut env, null_mut(), |env| {
+        let private_key = read_private_key(env, &private_key)?;
+        return Ok(env.byte_array_from_slice(&private_key.public_key())?.into_raw());
+    });
+}
+
+/// `static native byte[] getSharedSecret(byte[] privateKey, byte[] publicKey)`
+///
+/// The private key is clamped first, as for `PrivateKey::shared_secret`.
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_getSharedSecret<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    private_key: JByteArray<'local>,
+    public_key: JByteArray<'local>,
+) -> jbyteArray {
+    return throwing(&mut env, null_mut(), |env| {
+        let private_key = read_private_key(env, &private_key)?;
+        let public_key = read_array::<32>(env, &public_key, "publicKey")?;
+        return Ok(env.byte_array_from_slice(private_key.shared_secret(&public_key).as_bytes())?.into_raw());
+    });
+}
+
+/// `static native byte[] sign(byte[] privateKey, byte[] messageSha256)`
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_sign<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    private_key: JByteArray<'local>,
+    message_sha256: JByteArray<'local>,
+) -> jbyteArray {
+    return throwing(&mut env, null_mut(), |env| {
+        let private_key = read_private_key(env, &private_key)?;
+        let message_sha256 = read_array::<32>(env, &message_sha256, "messageSha256")?;
+        return Ok(env.byte_array_from_slice(&private_key.sign(&message_sha256))?.into_raw());
+    });
+}
+
+/// `static native boolean verify(byte[] publicKey, byte[] signature, byte[] messageSha256, boolean enforceCanonical)`
+#[no_mangle]
+pub extern "system" fn Java_burst_kit_crypto_LibShabal_verify<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    public_key: JByteArray<'local>,
+    signature: JByteArray<'local>,
+    message_sha256: JByteArray<'local>,
+    enforce_canonical: jboolean,
+) -> jboolean {
+    return throwing(&mut env, JNI_FALSE, |env| {
+        let public_key = read_array::<32>(env, &public_key, "publicKey")?;
+        let signature = read_array::<64>(env, &signature, "signature")?;
+        let message_sha256 = read_array::<32>(env, &message_sha256, "messageSha256")?;
+        let valid = curve25519::verify(&public_key, &signature, &message_sha256, enforce_canonical != JNI_FALSE);
+        return Ok(if valid { JNI_TRUE } else { JNI_FALSE });
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn illegal_argument(result: Result<impl Sized, Error>) -> String {
+        return match result {
+            Err(Error::IllegalArgument(message)) => message,
+            _ => panic!("expected an illegal argument"),
+        };
+    }
+
+    #[test]
+    fn lengths_are_checked() {
+        assert!(check_length("gensig", 32, 32).is_ok());
+        assert_eq!(illegal_argument(check_length("gensig", 32, 31)), "gensig must be 32 bytes, not 31");
+        assert_eq!(illegal_argument(check_length("signature", 64, 65)), "signature must be 64 bytes, not 65");
+    }
+
+    #[test]
+    fn nonce_counts_must_fit_the_scoops() {
+        assert_eq!(check_nonce_count(0, 0).ok(), Some(0));
+        assert_eq!(check_nonce_count(100, 100 * SCOOP_SIZE).ok(), Some(100));
+        assert_eq!(check_nonce_count(99, 100 * SCOOP_SIZE + 1).ok(), Some(99));
+        assert_eq!(illegal_argument(check_nonce_count(101, 100 * SCOOP_SIZE)), format!("101 nonces do not fit in {} bytes of scoops", 100 * SCOOP_SIZE));
+        assert!(check_nonce_count(-1, usize::MAX).is_err());
+        assert!(check_nonce_count(i64::MAX, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn errors_map_to_exceptions() {
+        assert_eq!(exception(Error::IllegalArgument("gensig must be 32 bytes, not 31".to_string())), (
+            "java/lang/IllegalArgumentException",
+            "gensig must be 32 bytes, not 31".to_string(),
+        ));
+        assert_eq!(exception(Error::Jni(JniError::NullPtr("array"))).0, "java/lang/NullPointerException");
+        assert_eq!(exception(Error::Jni(JniError::WrongJValueType("int", "long"))).0, "java/lang/RuntimeException");
+        assert_eq!(exception(Error::Panic).0, "java/lang/RuntimeException");
+    }
+}
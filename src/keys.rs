@@ -0,0 +1,150 @@
+//! Secret key material which is wiped from memory when dropped.
+
+use crate::curve25519;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// A 32 byte Curve25519 private key.
+///
+/// The key is zeroized when dropped and is not printed by `Debug`.
+#[derive(Clone)]
+pub struct PrivateKey([u8; 32]);
+
+impl PrivateKey {
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        return PrivateKey(*bytes);
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        return &self.0;
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        let mut public_key = [0u8; 32];
+        curve25519::get_public_key(&self.0, &mut public_key);
+        return public_key;
+    }
+
+    /// The secret shared with the owner of `public_key`.
+    ///
+    /// The key is clamped first, as it is for key generation, so both parties arrive at the same secret
+    /// whether or not their keys were clamped beforehand.
+    pub fn shared_secret(&self, public_key: &[u8; 32]) -> SharedSecret {
+        let mut shared_secret = SharedSecret([0u8; 32]);
+        curve25519::get_shared_secret(&self.0, public_key, &mut shared_secret.0);
+        return shared_secret;
+    }
+
+    /// Signs the SHA-256 hash of a message.
+    pub fn sign(&self, message_sha256: &[u8; 32]) -> [u8; 64] {
+        let mut signature = [0u8; 64];
+        curve25519::sign(&self.0, message_sha256, &mut signature);
+        return signature;
+    }
+
+    /// The EC-KCDSA signing key of this key, which signs exactly as this key does.
+    pub fn signing_key(&self) -> SigningKey {
+        let mut signing_key = SigningKey([0u8; 32]);
+        curve25519::get_signing_key(&self.0, &mut signing_key.0);
+        return signing_key;
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.write_str("PrivateKey(..)");
+    }
+}
+
+/// A 32 byte EC-KCDSA signing key, the scalar s derived from a private key during key generation.
+///
+/// It can sign but not agree on a shared secret. The key is zeroized when dropped and is not printed by `Debug`.
+#[derive(Clone)]
+pub struct SigningKey([u8; 32]);
+
+impl SigningKey {
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        return SigningKey(*bytes);
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        return &self.0;
+    }
+
+    /// Signs the SHA-256 hash of a message.
+    pub fn sign(&self, message_sha256: &[u8; 32]) -> [u8; 64] {
+        let mut signature = [0u8; 64];
+        curve25519::sign_with_signing_key(&self.0, message_sha256, &mut signature);
+        return signature;
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.write_str("SigningKey(..)");
+    }
+}
+
+/// A 32 byte Curve25519 shared secret.
+///
+/// The secret is zeroized when dropped and is not printed by `Debug`.
+#[derive(Clone)]
+pub struct SharedSecret([u8; 32]);
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        return &self.0;
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.write_str("SharedSecret(..)");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keys_agree_and_sign() {
+        let alice = PrivateKey::from_bytes(&[0x11; 32]);
+        let bob = PrivateKey::from_bytes(&[0x22; 32]);
+        assert_eq!(alice.shared_secret(&bob.public_key()).as_bytes(), bob.shared_secret(&alice.public_key()).as_bytes());
+
+        let message_sha256 = [0x13u8; 32];
+        let signature = alice.sign(&message_sha256);
+        assert!(curve25519::verify(&alice.public_key(), &signature, &message_sha256, true));
+        assert_eq!(format!("{:?}", alice), "PrivateKey(..)");
+    }
+
+    #[test]
+    fn signing_key_signs_like_private_key() {
+        let private_key = PrivateKey::from_bytes(&[0x11; 32]);
+        let signing_key = private_key.signing_key();
+        assert_ne!(signing_key.as_bytes(), private_key.as_bytes());
+
+        let message_sha256 = [0x13u8; 32];
+        assert_eq!(signing_key.sign(&message_sha256)[..], private_key.sign(&message_sha256)[..]);
+        assert_eq!(format!("{:?}", signing_key), "SigningKey(..)");
+    }
+}
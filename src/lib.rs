@@ -1,17 +1,42 @@
+#![allow(clippy::needless_return)]
+
 use cfg_if::cfg_if;
 use std::sync::{Once};
-use std::{u64, slice};
-use std::os::raw::c_void;
+use std::slice;
+use std::os::raw::{c_char, c_void};
 use pocc::plot::NONCE_SIZE;
 use pocc::plot::SCOOP_SIZE;
+use pocc::plot::NUM_SCOOPS;
 use crate::simd::SimdExtension;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use once_cell::sync::Lazy;
+use std::ptr::{null, null_mut};
+use std::sync::atomic::AtomicU8;
+use crate::cancel::{CancellationToken, ProgressCallback};
+use crate::status::libshabal_status;
+use crate::keys::PrivateKey;
+use crate::signature::MessageHasher;
+use crate::encrypted_data::EncryptedData;
+use zeroize::Zeroizing;
+use crate::shabal::Digest;
 
 mod pocc;
 mod simd;
-mod shabal;
+pub mod shabal;
 mod curve25519;
+pub mod cancel;
+pub mod status;
+pub mod nonce;
+pub mod account;
+pub mod address;
+pub mod keys;
+pub mod signature;
+pub mod encrypted_data;
+pub mod transaction;
+#[cfg(feature = "jni")]
+mod java;
+
+pub use pocc::shabal256_fast::Shabal256Midstate;
 
 extern "C" {
     pub fn find_best_deadline_sph(
@@ -20,6 +45,7 @@ extern "C" {
         gensig: *const u8,
         best_deadline: *mut u64,
         best_offset: *mut u64,
+        cancelled: *const u8,
     );
 }
 
@@ -33,6 +59,13 @@ cfg_if! {
                 gensig: *const u8,
                 best_deadline: *mut u64,
                 best_offset: *mut u64,
+                cancelled: *const u8,
+            );
+            pub fn hash_lanes_avx512f(
+                inputs: *const *const u8,
+                len: usize,
+                lane_count: usize,
+                outputs: *const *mut u8,
             );
 
             pub fn init_shabal_avx2();
@@ -42,6 +75,13 @@ cfg_if! {
                 gensig: *const u8,
                 best_deadline: *mut u64,
                 best_offset: *mut u64,
+                cancelled: *const u8,
+            );
+            pub fn hash_lanes_avx2(
+                inputs: *const *const u8,
+                len: usize,
+                lane_count: usize,
+                outputs: *const *mut u8,
             );
 
             pub fn init_shabal_avx();
@@ -51,6 +91,13 @@ cfg_if! {
                 gensig: *const u8,
                 best_deadline: *mut u64,
                 best_offset: *mut u64,
+                cancelled: *const u8,
+            );
+            pub fn hash_lanes_avx(
+                inputs: *const *const u8,
+                len: usize,
+                lane_count: usize,
+                outputs: *const *mut u8,
             );
 
             pub fn init_shabal_sse2();
@@ -60,6 +107,13 @@ cfg_if! {
                 gensig: *const u8,
                 best_deadline: *mut u64,
                 best_offset: *mut u64,
+                cancelled: *const u8,
+            );
+            pub fn hash_lanes_sse2(
+                inputs: *const *const u8,
+                len: usize,
+                lane_count: usize,
+                outputs: *const *mut u8,
             );
 
             pub fn init_noncegen_sse2();
@@ -69,6 +123,7 @@ cfg_if! {
                 local_startnonce: u64,
                 local_nonces: u64,
                 poc_version: u8,
+                cancelled: *const u8,
             );
 
             pub fn init_noncegen_avx();
@@ -78,6 +133,7 @@ cfg_if! {
                 local_startnonce: u64,
                 local_nonces: u64,
                 poc_version: u8,
+                cancelled: *const u8,
             );
 
             pub fn init_noncegen_avx2();
@@ -87,6 +143,7 @@ cfg_if! {
                 local_startnonce: u64,
                 local_nonces: u64,
                 poc_version: u8,
+                cancelled: *const u8,
             );
 
             pub fn init_noncegen_avx512f();
@@ -96,6 +153,7 @@ cfg_if! {
                 local_startnonce: u64,
                 local_nonces: u64,
                 poc_version: u8,
+                cancelled: *const u8,
             );
         }
     }
@@ -111,6 +169,13 @@ cfg_if! {
                 gensig: *const u8,
                 best_deadline: *mut u64,
                 best_offset: *mut u64,
+                cancelled: *const u8,
+            );
+            pub fn hash_lanes_neon(
+                inputs: *const *const u8,
+                len: usize,
+                lane_count: usize,
+                outputs: *const *mut u8,
             );
         }
     }
@@ -121,43 +186,226 @@ static VERSION: Lazy<CString> = Lazy::new(|| {
 });
 
 #[no_mangle]
-pub extern fn libshabal_version() -> *const u8 {
-    return VERSION.as_ptr() as *const u8;
+pub extern "C" fn libshabal_version() -> *const u8 {
+    return status::catch_panic(null(), || VERSION.as_ptr() as *const u8);
+}
+
+/// Returns a null-terminated description of `status`, a `libshabal_status` value.
+///
+/// A value which is not a status, such as an uninitialized variable, gives a description saying so.
+#[no_mangle]
+pub extern "C" fn libshabal_status_string(status: u32) -> *const u8 {
+    return status::catch_panic(null(), || {
+        return match libshabal_status::from_u32(status) {
+            Some(status) => status.description().as_ptr(),
+            None => "Unknown status\0".as_ptr(),
+        };
+    });
+}
+
+#[no_mangle]
+pub extern "C" fn shabal_findBestDeadlineDirect(
+    scoops: *const u8,
+    nonce_count: u64,
+    gensig: *const u8,
+    best_deadline: *mut u64,
+    best_offset: *mut u64,
+) {
+    status::catch_panic((), || {
+        if scoops.is_null() || gensig.is_null() || best_deadline.is_null() || best_offset.is_null() { return; }
+        find_best_deadline_dispatch(scoops, nonce_count, gensig, best_deadline, best_offset, null());
+    });
+}
+
+/// Finds the best deadline like `shabal_findBestDeadlineDirect`, but can be stopped part way through.
+///
+/// `cancelled` points to a flag which is polled between SIMD batches - set it to non-zero from
+/// another thread to stop the search. Pass null if cancellation is not needed.
+///
+/// `progress` is called with `progress_user_data` and the number of nonces processed so far after every
+/// chunk of `DEADLINE_CHUNK_SIZE` nonces. Pass null if progress reporting is not needed.
+///
+/// Returns 1 if all nonces were processed and 0 if the search was cancelled or a required pointer was null,
+/// in which case `best_deadline` and `best_offset` are left untouched.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn shabal_findBestDeadlineCancellable(
+    scoops: *const u8,
+    nonce_count: u64,
+    gensig: *const u8,
+    best_deadline: *mut u64,
+    best_offset: *mut u64,
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> u8 {
+    return status::catch_panic(0, || {
+        if scoops.is_null() || gensig.is_null() || best_deadline.is_null() || best_offset.is_null() { return 0; }
+        let completed = unsafe {
+            find_best_deadline_with_callback(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled, progress, progress_user_data)
+        };
+        return if completed { 1 } else { 0 };
+    });
 }
 
+/// Checked variant of `shabal_findBestDeadlineDirect` and `shabal_findBestDeadlineCancellable`.
+///
+/// `scoops_len` is the size of `scoops` in bytes and must hold `nonce_count` scoops, `gensig_len` must be 32.
+/// `cancelled`, `progress` and `progress_user_data` behave as in `shabal_findBestDeadlineCancellable` and may be null.
+///
+/// Returns `CANCELLED` if the search was stopped, in which case `best_deadline` and `best_offset` are left untouched.
 #[no_mangle]
-pub extern fn shabal_findBestDeadlineDirect(
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn shabal_findBestDeadlineChecked(
+    scoops: *const u8,
+    scoops_len: usize,
+    nonce_count: u64,
+    gensig: *const u8,
+    gensig_len: usize,
+    best_deadline: *mut u64,
+    best_offset: *mut u64,
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> libshabal_status {
+    return status::run(|| {
+        let scoops_size = (nonce_count as usize).checked_mul(SCOOP_SIZE).ok_or(libshabal_status::INVALID_ARGUMENT)?;
+        status::check_buffer(scoops, scoops_len, 0, scoops_size)?;
+        status::check_input(gensig, gensig_len, 32)?;
+        status::check_not_null(best_deadline)?;
+        status::check_not_null(best_offset)?;
+        let completed = unsafe {
+            find_best_deadline_with_callback(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled, progress, progress_user_data)
+        };
+        return if completed { Ok(()) } else { Err(libshabal_status::CANCELLED) };
+    });
+}
+
+/// Number of nonces between progress reports and cancellation checks on the Rust side.
+/// The SIMD kernels also poll the cancellation flag between each of their own batches.
+pub const DEADLINE_CHUNK_SIZE: u64 = 16384;
+
+/// Finds the best deadline among `scoops`, stopping early if `token` is cancelled.
+///
+/// `scoops` holds one 64 byte scoop per nonce. `progress` receives the number of nonces processed so far
+/// after every chunk of `DEADLINE_CHUNK_SIZE` nonces.
+///
+/// Returns the best deadline and its offset, or `None` if the search was cancelled.
+pub fn find_best_deadline(
+    scoops: &[u8],
+    gensig: &[u8; 32],
+    token: &CancellationToken,
+    progress: &mut dyn FnMut(u64),
+) -> Option<(u64, u64)> {
+    let nonce_count = (scoops.len() / SCOOP_SIZE) as u64;
+    unsafe {
+        return find_best_deadline_chunked(scoops.as_ptr(), nonce_count, gensig.as_ptr(), Some(token.flag()), progress);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe fn find_best_deadline_with_callback(
+    scoops: *const u8,
+    nonce_count: u64,
+    gensig: *const u8,
+    best_deadline: *mut u64,
+    best_offset: *mut u64,
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> bool {
+    let cancelled = (cancelled as *const AtomicU8).as_ref();
+    let mut report = |processed: u64| {
+        if let Some(progress) = progress { progress(processed, progress_user_data); }
+    };
+    return match find_best_deadline_chunked(scoops, nonce_count, gensig, cancelled, &mut report) {
+        Some((deadline, offset)) => {
+            *best_deadline = deadline;
+            *best_offset = offset;
+            true
+        },
+        None => false,
+    };
+}
+
+unsafe fn find_best_deadline_chunked(
+    scoops: *const u8,
+    nonce_count: u64,
+    gensig: *const u8,
+    cancelled: Option<&AtomicU8>,
+    progress: &mut dyn FnMut(u64),
+) -> Option<(u64, u64)> {
+    let mut best_deadline = u64::MAX;
+    let mut best_offset = 0;
+    let mut processed = 0;
+    while processed < nonce_count {
+        if cancel::is_set(cancelled) { return None; }
+        let chunk_size = DEADLINE_CHUNK_SIZE.min(nonce_count - processed);
+        let mut chunk_deadline = u64::MAX;
+        let mut chunk_offset = 0;
+        find_best_deadline_dispatch(
+            scoops.add(processed as usize * SCOOP_SIZE),
+            chunk_size,
+            gensig,
+            &mut chunk_deadline,
+            &mut chunk_offset,
+            cancel::flag_ptr(cancelled),
+        );
+        // the kernel may have stopped part way through the chunk
+        if cancel::is_set(cancelled) { return None; }
+        if chunk_deadline < best_deadline {
+            best_deadline = chunk_deadline;
+            best_offset = processed + chunk_offset;
+        }
+        processed += chunk_size;
+        progress(processed);
+    }
+    return Some((best_deadline, best_offset));
+}
+
+fn find_best_deadline_dispatch(
     scoops: *const u8,
     nonce_count: u64,
     gensig: *const u8,
     best_deadline: *mut u64,
     best_offset: *mut u64,
+    cancelled: *const u8,
 ) {
+    init_kernels();
     let supported_extension: &SimdExtension = &simd::SUPPORTED_SIMD_EXTENSION;
     unsafe {
         match supported_extension {
             simd::SimdExtension::AVX512f => {
-                #[cfg(feature = "simd")] find_best_deadline_avx512f(scoops, nonce_count, gensig, best_deadline, best_offset);
+                #[cfg(feature = "simd")] find_best_deadline_avx512f(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled);
             },
             simd::SimdExtension::AVX2 => {
-                #[cfg(feature = "simd")] find_best_deadline_avx2(scoops, nonce_count, gensig, best_deadline, best_offset);
+                #[cfg(feature = "simd")] find_best_deadline_avx2(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled);
             },
             simd::SimdExtension::AVX => {
-                #[cfg(feature = "simd")] find_best_deadline_avx(scoops, nonce_count, gensig, best_deadline, best_offset);
+                #[cfg(feature = "simd")] find_best_deadline_avx(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled);
             },
             simd::SimdExtension::SSE2 => {
-                #[cfg(feature = "simd")] find_best_deadline_sse2(scoops, nonce_count, gensig, best_deadline, best_offset);
+                #[cfg(feature = "simd")] find_best_deadline_sse2(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled);
             },
             simd::SimdExtension::NEON => {
-                #[cfg(feature = "neon")] find_best_deadline_neon(scoops, nonce_count, gensig, best_deadline, best_offset);
+                #[cfg(feature = "neon")] find_best_deadline_neon(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled);
             },
-            simd::SimdExtension::NONE => find_best_deadline_sph(scoops, nonce_count, gensig, best_deadline, best_offset),
+            simd::SimdExtension::NONE => find_best_deadline_sph(scoops, nonce_count, gensig, best_deadline, best_offset, cancelled),
         }
     }
 }
 
+/// Sets up the SIMD kernels.
+///
+/// Calling this is optional - every function which uses the kernels initializes them on first use.
+/// It can still be called up front to move the one-off setup cost out of the first deadline or plot call.
 #[no_mangle]
-pub extern fn shabal_init() {
+pub extern "C" fn shabal_init() {
+    status::catch_panic((), init_kernels);
+}
+
+/// Initializes the global state of the SIMD kernels exactly once, however many threads race to call it.
+fn init_kernels() {
     static INITIALIZE: Once = Once::new();
     INITIALIZE.call_once(|| {
         let supported_extension: &SimdExtension = &simd::SUPPORTED_SIMD_EXTENSION;
@@ -197,15 +445,165 @@ pub extern fn shabal_init() {
 }
 
 #[no_mangle]
-pub extern fn shabal_findBestDeadline(
+pub extern "C" fn shabal_findBestDeadline(
     scoops: *const u8,
     nonce_count: u64,
     gensig: *const u8,
 ) -> u64 {
-    let mut deadline: u64 = u64::MAX;
-    let mut offset: u64 = 0;
-    shabal_findBestDeadlineDirect(scoops, nonce_count, gensig, &mut deadline, &mut offset);
-    return offset;
+    return status::catch_panic(0, || {
+        let mut deadline: u64 = u64::MAX;
+        let mut offset: u64 = 0;
+        shabal_findBestDeadlineDirect(scoops, nonce_count, gensig, &mut deadline, &mut offset);
+        return offset;
+    });
+}
+
+/// Hashes `len` bytes of `data` with Shabal256, storing the 32 byte hash in `out`.
+///
+/// `out` must have 32 bytes available otherwise this will attempt to write beyond the array.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn shabal256_hash(data: *const u8, len: usize, out: *mut u8) {
+    status::catch_panic((), || {
+        if (data.is_null() && len != 0) || out.is_null() { return; }
+        unsafe {
+            let out_borrowed = slice::from_raw_parts_mut(out, 32);
+            out_borrowed.copy_from_slice(&shabal::Shabal256::digest(input_slice(data, len)));
+        }
+    });
+}
+
+/// Checked variant of `shabal256_hash`.
+///
+/// `out_len` is the size of `out` in bytes, which must be at least 32.
+#[no_mangle]
+pub extern "C" fn shabal256_hash_checked(data: *const u8, len: usize, out: *mut u8, out_len: usize) -> libshabal_status {
+    return status::run(|| {
+        if len != 0 { status::check_not_null(data)?; }
+        status::check_buffer(out, out_len, 0, 32)?;
+        shabal256_hash(data, len, out);
+        return Ok(());
+    });
+}
+
+/// Hashes `count` independent messages with Shabal256.
+///
+/// `inputs` and `lengths` are arrays of `count` message pointers and lengths.
+/// The hash of message `i` is stored in `outs` starting from `i * 32`, so `outs` must have `count * 32` bytes available.
+///
+/// Messages of equal length are hashed in parallel on the SIMD kernels, so batches of same-length messages are fastest.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn shabal256_hash_many(inputs: *const *const u8, lengths: *const usize, count: usize, outs: *mut u8) {
+    status::catch_panic((), || {
+        if count == 0 || inputs.is_null() || lengths.is_null() || outs.is_null() { return; }
+        unsafe {
+            let inputs_borrowed = slice::from_raw_parts(inputs, count);
+            let lengths_borrowed = slice::from_raw_parts(lengths, count);
+            if inputs_borrowed.iter().zip(lengths_borrowed).any(|(input, &len)| input.is_null() && len != 0) { return; }
+            let messages: Vec<&[u8]> = inputs_borrowed.iter().zip(lengths_borrowed).map(|(&input, &len)| input_slice(input, len)).collect();
+            let outs_borrowed = slice::from_raw_parts_mut(outs as *mut [u8; 32], count);
+            shabal256_hash_many_into(&messages, outs_borrowed);
+        }
+    });
+}
+
+/// Checked variant of `shabal256_hash_many`.
+///
+/// `outs_len` is the size of `outs` in bytes, which must be at least `count * 32`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn shabal256_hash_many_checked(
+    inputs: *const *const u8,
+    lengths: *const usize,
+    count: usize,
+    outs: *mut u8,
+    outs_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if count == 0 { return Ok(()); }
+        status::check_not_null(inputs)?;
+        status::check_not_null(lengths)?;
+        let outs_size = count.checked_mul(32).ok_or(libshabal_status::INVALID_ARGUMENT)?;
+        status::check_buffer(outs, outs_len, 0, outs_size)?;
+        unsafe {
+            let inputs_borrowed = slice::from_raw_parts(inputs, count);
+            let lengths_borrowed = slice::from_raw_parts(lengths, count);
+            if inputs_borrowed.iter().zip(lengths_borrowed).any(|(input, &len)| input.is_null() && len != 0) {
+                return Err(libshabal_status::NULL_POINTER);
+            }
+        }
+        shabal256_hash_many(inputs, lengths, count, outs);
+        return Ok(());
+    });
+}
+
+/// Hashes each of `inputs` with Shabal256, storing the hashes in the matching entries of `outputs`.
+///
+/// Messages of equal length are hashed in parallel on the SIMD kernels,
+/// any message whose length is not shared by another is hashed on its own.
+///
+/// Panics if `inputs` and `outputs` have different lengths.
+pub fn shabal256_hash_many_into(inputs: &[&[u8]], outputs: &mut [[u8; 32]]) {
+    assert_eq!(inputs.len(), outputs.len(), "every input needs an output");
+    let lanes = simd::SUPPORTED_SIMD_EXTENSION.lanes();
+    let mut order: Vec<usize> = (0..inputs.len()).collect();
+    order.sort_by_key(|&i| inputs[i].len());
+
+    let mut start = 0;
+    while start < order.len() {
+        let len = inputs[order[start]].len();
+        let mut end = start + 1;
+        while end < order.len() && end - start < lanes && inputs[order[end]].len() == len {
+            end += 1;
+        }
+        let batch = &order[start..end];
+        if batch.len() == 1 {
+            outputs[batch[0]].copy_from_slice(&shabal::Shabal256::digest(inputs[batch[0]]));
+        } else {
+            let mut lane_inputs = [null(); MAX_LANES];
+            let mut lane_outputs = [null_mut(); MAX_LANES];
+            for (lane, &i) in batch.iter().enumerate() {
+                lane_inputs[lane] = inputs[i].as_ptr();
+                lane_outputs[lane] = outputs[i].as_mut_ptr();
+            }
+            hash_lanes_dispatch(lane_inputs.as_ptr(), len, batch.len(), lane_outputs.as_ptr());
+        }
+        start = end;
+    }
+}
+
+/// Widest SIMD batch of any kernel.
+const MAX_LANES: usize = 16;
+
+#[cfg_attr(not(any(feature = "simd", feature = "neon")), allow(unused_variables, unused_unsafe))]
+fn hash_lanes_dispatch(inputs: *const *const u8, len: usize, lane_count: usize, outputs: *const *mut u8) {
+    let supported_extension: &SimdExtension = &simd::SUPPORTED_SIMD_EXTENSION;
+    unsafe {
+        match supported_extension {
+            simd::SimdExtension::AVX512f => {
+                #[cfg(feature = "simd")] hash_lanes_avx512f(inputs, len, lane_count, outputs);
+            },
+            simd::SimdExtension::AVX2 => {
+                #[cfg(feature = "simd")] hash_lanes_avx2(inputs, len, lane_count, outputs);
+            },
+            simd::SimdExtension::AVX => {
+                #[cfg(feature = "simd")] hash_lanes_avx(inputs, len, lane_count, outputs);
+            },
+            simd::SimdExtension::SSE2 => {
+                #[cfg(feature = "simd")] hash_lanes_sse2(inputs, len, lane_count, outputs);
+            },
+            simd::SimdExtension::NEON => {
+                #[cfg(feature = "neon")] hash_lanes_neon(inputs, len, lane_count, outputs);
+            },
+            simd::SimdExtension::NONE => unreachable!("messages are hashed one at a time without SIMD"),
+        }
+    }
+}
+
+/// Borrows `len` bytes from `data`, which may be null if `len` is 0.
+unsafe fn input_slice<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    return if len == 0 { &[] } else { slice::from_raw_parts(data, len) };
 }
 
 /// Create a new Shabal256 instance
@@ -217,24 +615,101 @@ pub extern fn shabal_findBestDeadline(
 /// This implementation of Shabal256 is not particularly
 /// fast but is here for completeness.
 #[no_mangle]
-pub extern fn shabal256_new() -> *mut c_void {
-    return shabal::shabal256_new()
+pub extern "C" fn shabal256_new() -> *mut c_void {
+    return status::catch_panic(null_mut(), shabal::shabal256_new);
+}
+
+/// Size in bytes of a Shabal256 instance.
+///
+/// Together with `libshabal_shabal256_context_align()` this lets callers allocate instances
+/// in their own memory (eg. on the stack) and set them up with `shabal256_init_in_place()`.
+#[no_mangle]
+pub extern "C" fn libshabal_shabal256_context_size() -> usize {
+    return shabal::shabal256_context_size();
+}
+
+/// Alignment in bytes required of memory holding a Shabal256 instance.
+#[no_mangle]
+pub extern "C" fn libshabal_shabal256_context_align() -> usize {
+    return shabal::shabal256_context_align();
+}
+
+/// Initialize a Shabal256 instance in caller owned memory.
+///
+/// `buffer` must have `libshabal_shabal256_context_size()` bytes available and be aligned
+/// to `libshabal_shabal256_context_align()`, otherwise nothing is written.
+/// The instance can then be used with the other Shabal256 functions in place of a pointer returned from `shabal256_new()`.
+///
+/// Do not pass the instance to `shabal256_destroy()` - it owns no other memory, so the caller just releases `buffer` when done.
+#[no_mangle]
+pub extern "C" fn shabal256_init_in_place(buffer: *mut c_void) {
+    status::catch_panic((), || shabal::shabal256_init_in_place(buffer));
+}
+
+/// Checked variant of `shabal256_init_in_place`.
+///
+/// `buffer_len` is the size of `buffer` in bytes. A misaligned `buffer` is reported as `INVALID_ARGUMENT`.
+#[no_mangle]
+pub extern "C" fn shabal256_init_in_place_checked(buffer: *mut c_void, buffer_len: usize) -> libshabal_status {
+    return status::run(|| {
+        check_context_buffer(buffer, buffer_len)?;
+        shabal::shabal256_init_in_place(buffer);
+        return Ok(());
+    });
+}
+
+/// Copy the state of a Shabal256 instance into `destination`.
+///
+/// This forks the midstate, so a common prefix only needs to be hashed once.
+/// `destination` is either another instance or memory set up as described for `shabal256_init_in_place()`,
+/// and is used in the same way afterwards.
+#[no_mangle]
+pub extern "C" fn shabal256_clone(shabal: *const c_void, destination: *mut c_void) {
+    status::catch_panic((), || shabal::shabal256_clone(shabal, destination));
+}
+
+/// Checked variant of `shabal256_clone`.
+///
+/// `destination_len` is the size of `destination` in bytes.
+#[no_mangle]
+pub extern "C" fn shabal256_clone_checked(shabal: *const c_void, destination: *mut c_void, destination_len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        check_context_buffer(destination, destination_len)?;
+        shabal::shabal256_clone(shabal, destination);
+        return Ok(());
+    });
+}
+
+fn check_context_buffer(buffer: *mut c_void, buffer_len: usize) -> Result<(), libshabal_status> {
+    status::check_buffer(buffer as *const u8, buffer_len, 0, shabal::shabal256_context_size())?;
+    return if shabal::is_aligned::<shabal::Shabal256>(buffer) { Ok(()) } else { Err(libshabal_status::INVALID_ARGUMENT) };
 }
 
 /// Destroy a Shabal256 instance, clearing memory allocated for it.
 ///
 /// `shabal` is the pointer to the instance returned from `shabal256_new()`
 #[no_mangle]
-pub extern fn shabal256_destroy(shabal: *mut c_void) {
-    shabal::shabal256_destroy(shabal)
+pub extern "C" fn shabal256_destroy(shabal: *mut c_void) {
+    status::catch_panic((), || shabal::shabal256_destroy(shabal));
 }
 
 /// Reset a Shabal256 instance to its initial state
 ///
 /// `shabal` is the pointer to the instance returned from `shabal256_new()`
 #[no_mangle]
-pub extern fn shabal256_reset(shabal: *mut c_void) {
-    shabal::shabal256_reset(shabal);
+pub extern "C" fn shabal256_reset(shabal: *mut c_void) {
+    status::catch_panic((), || shabal::shabal256_reset(shabal));
+}
+
+/// Checked variant of `shabal256_reset`.
+#[no_mangle]
+pub extern "C" fn shabal256_reset_checked(shabal: *mut c_void) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        shabal::shabal256_reset(shabal);
+        return Ok(());
+    });
 }
 
 /// Update a Shabal256 instance with new input data
@@ -243,8 +718,21 @@ pub extern fn shabal256_reset(shabal: *mut c_void) {
 ///
 /// Inputs data into the digest from `data` starting at `offset` of length `len`
 #[no_mangle]
-pub extern fn shabal256_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
-    shabal::shabal256_update(shabal, data, offset, len);
+pub extern "C" fn shabal256_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    status::catch_panic((), || shabal::shabal256_update(shabal, data, offset, len));
+}
+
+/// Checked variant of `shabal256_update`.
+///
+/// `data_len` is the size of `data` in bytes, which must contain `len` bytes from `offset`.
+#[no_mangle]
+pub extern "C" fn shabal256_update_checked(shabal: *mut c_void, data: *const u8, data_len: usize, offset: usize, len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        status::check_buffer(data, data_len, offset, len)?;
+        shabal::shabal256_update(shabal, data, offset, len);
+        return Ok(());
+    });
 }
 
 /// Retrieve the result of a Shabal256 digest and reset the digest.
@@ -253,8 +741,103 @@ pub extern fn shabal256_update(shabal: *mut c_void, data: *const u8, offset: usi
 ///
 /// `buffer` must have 32 bytes available from `offset` otherwise this will attempt to write beyond the array.
 #[no_mangle]
-pub extern fn shabal256_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
-    shabal::shabal256_digest(shabal, buffer, offset);
+pub extern "C" fn shabal256_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
+    status::catch_panic((), || shabal::shabal256_digest(shabal, buffer, offset));
+}
+
+/// Checked variant of `shabal256_digest`.
+///
+/// `buffer_len` is the size of `buffer` in bytes, which must have 32 bytes available from `offset`.
+#[no_mangle]
+pub extern "C" fn shabal256_digest_checked(shabal: *mut c_void, buffer: *mut u8, buffer_len: usize, offset: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        status::check_buffer(buffer, buffer_len, offset, 32)?;
+        shabal::shabal256_digest(shabal, buffer, offset);
+        return Ok(());
+    });
+}
+
+/// Create a new Shabal512 instance
+///
+/// Returns a pointer to the instance, which
+/// can be used with the other `shabal512_` functions to
+/// manipulate the instance.
+#[no_mangle]
+pub extern "C" fn shabal512_new() -> *mut c_void {
+    return status::catch_panic(null_mut(), shabal::shabal512_new);
+}
+
+/// Destroy a Shabal512 instance, clearing memory allocated for it.
+///
+/// `shabal` is the pointer to the instance returned from `shabal512_new()`
+#[no_mangle]
+pub extern "C" fn shabal512_destroy(shabal: *mut c_void) {
+    status::catch_panic((), || shabal::shabal512_destroy(shabal));
+}
+
+/// Reset a Shabal512 instance to its initial state
+///
+/// `shabal` is the pointer to the instance returned from `shabal512_new()`
+#[no_mangle]
+pub extern "C" fn shabal512_reset(shabal: *mut c_void) {
+    status::catch_panic((), || shabal::shabal512_reset(shabal));
+}
+
+/// Checked variant of `shabal512_reset`.
+#[no_mangle]
+pub extern "C" fn shabal512_reset_checked(shabal: *mut c_void) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        shabal::shabal512_reset(shabal);
+        return Ok(());
+    });
+}
+
+/// Update a Shabal512 instance with new input data
+///
+/// `shabal` is the pointer to the instance returned from `shabal512_new()`
+///
+/// Inputs data into the digest from `data` starting at `offset` of length `len`
+#[no_mangle]
+pub extern "C" fn shabal512_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    status::catch_panic((), || shabal::shabal512_update(shabal, data, offset, len));
+}
+
+/// Checked variant of `shabal512_update`.
+///
+/// `data_len` is the size of `data` in bytes, which must contain `len` bytes from `offset`.
+#[no_mangle]
+pub extern "C" fn shabal512_update_checked(shabal: *mut c_void, data: *const u8, data_len: usize, offset: usize, len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        status::check_buffer(data, data_len, offset, len)?;
+        shabal::shabal512_update(shabal, data, offset, len);
+        return Ok(());
+    });
+}
+
+/// Retrieve the result of a Shabal512 digest and reset the digest.
+///
+/// Stores the data in `buffer` starting from `offset`. Stores 64 bytes of hash data.
+///
+/// `buffer` must have 64 bytes available from `offset` otherwise this will attempt to write beyond the array.
+#[no_mangle]
+pub extern "C" fn shabal512_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
+    status::catch_panic((), || shabal::shabal512_digest(shabal, buffer, offset));
+}
+
+/// Checked variant of `shabal512_digest`.
+///
+/// `buffer_len` is the size of `buffer` in bytes, which must have 64 bytes available from `offset`.
+#[no_mangle]
+pub extern "C" fn shabal512_digest_checked(shabal: *mut c_void, buffer: *mut u8, buffer_len: usize, offset: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(shabal)?;
+        status::check_buffer(buffer, buffer_len, offset, 64)?;
+        shabal::shabal512_digest(shabal, buffer, offset);
+        return Ok(());
+    });
 }
 
 /// Creates PoC Nonces, with SIMD instructions for extra speed.
@@ -263,7 +846,8 @@ pub extern fn shabal256_digest(shabal: *mut c_void, buffer: *mut u8, offset: usi
 ///
 /// `nonce_count` counts from 1 - 0 is no plots and will do nothing.
 #[no_mangle]
-pub extern fn create_plots(
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_plots(
     account_id: u64,
     start_nonce: u64,
     nonce_count: u64,
@@ -271,119 +855,1879 @@ pub extern fn create_plots(
     plot_buffer: *mut u8,
     plot_buffer_offset: usize,
 ) {
-    if nonce_count == 0 { return; }
-    let supported_extension: &SimdExtension = &simd::SUPPORTED_SIMD_EXTENSION;
-    unsafe {
-        let offset_plot_buffer = plot_buffer.add(plot_buffer_offset);
-        match supported_extension {
-            simd::SimdExtension::AVX512f => {
-                #[cfg(feature = "simd")]
-                    noncegen_avx512(offset_plot_buffer, account_id, start_nonce, nonce_count, poc_version);
-            },
-            simd::SimdExtension::AVX2 => {
-                #[cfg(feature = "simd")]
-                    noncegen_avx2(offset_plot_buffer, account_id, start_nonce, nonce_count, poc_version);
-            },
-            simd::SimdExtension::AVX => {
-                #[cfg(feature = "simd")]
-                    noncegen_avx(offset_plot_buffer, account_id, start_nonce, nonce_count, poc_version);
-            },
-            simd::SimdExtension::SSE2 => {
-                #[cfg(feature = "simd")]
-                    noncegen_sse2(offset_plot_buffer, account_id, start_nonce, nonce_count, poc_version);
-            },
-            _ => {
-                let plot_buffer_borrowed = slice::from_raw_parts_mut(offset_plot_buffer, NONCE_SIZE * nonce_count as usize);
-                pocc::plot::noncegen_rust(plot_buffer_borrowed, account_id, start_nonce, nonce_count, poc_version, );
-            }
+    status::catch_panic((), || {
+        if nonce_count == 0 || plot_buffer.is_null() { return; }
+        unsafe {
+            create_plots_dispatch(account_id, start_nonce, nonce_count, poc_version, plot_buffer.add(plot_buffer_offset), None);
         }
-    }
+    });
 }
 
-/// Creates a single PoC Nonce.
+/// Creates PoC Nonces like `create_plots`, but can be stopped part way through.
 ///
-/// `plot_buffer` must be correct size - no size checks are performed.
+/// `cancelled` points to a flag which is polled between SIMD batches - set it to non-zero from
+/// another thread to stop plotting. Pass null if cancellation is not needed.
+///
+/// `progress` is called with `progress_user_data` and the number of nonces completed so far after every
+/// chunk of `PLOT_CHUNK_SIZE` nonces. Pass null if progress reporting is not needed.
+///
+/// Returns 1 if all nonces were created and 0 if plotting was cancelled or `plot_buffer` was null,
+/// in which case the contents of `plot_buffer` are incomplete.
 #[no_mangle]
-pub extern fn create_plot(
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_plots_cancellable(
     account_id: u64,
-    nonce: u64,
+    start_nonce: u64,
+    nonce_count: u64,
     poc_version: u8,
     plot_buffer: *mut u8,
     plot_buffer_offset: usize,
-) {
-    unsafe {
-        let offset_plot_buffer = plot_buffer.add(plot_buffer_offset);
-        pocc::plot::noncegen_single_rust(
-            slice::from_raw_parts_mut(offset_plot_buffer, NONCE_SIZE as usize),
-            account_id,
-            nonce,
-            poc_version,
-        );
-    }
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> u8 {
+    return status::catch_panic(0, || {
+        if plot_buffer.is_null() { return 0; }
+        let completed = unsafe {
+            let offset_plot_buffer = plot_buffer.add(plot_buffer_offset);
+            create_plots_with_callback(account_id, start_nonce, nonce_count, poc_version, offset_plot_buffer, cancelled, progress, progress_user_data)
+        };
+        return if completed { 1 } else { 0 };
+    });
 }
 
-/// Creates a single PoC Scoop.
+/// Checked variant of `create_plots` and `create_plots_cancellable`.
 ///
-/// `plot_buffer` must be correct size - no size checks are performed.
+/// `plot_buffer_len` is the size of `plot_buffer` in bytes, which must have room for `nonce_count` nonces from
+/// `plot_buffer_offset`. `poc_version` must be 1 or 2.
+/// `cancelled`, `progress` and `progress_user_data` behave as in `create_plots_cancellable` and may be null.
+///
+/// Returns `CANCELLED` if plotting was stopped, in which case the contents of `plot_buffer` are incomplete.
 #[no_mangle]
-pub extern fn create_scoop(
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_plots_checked(
     account_id: u64,
-    nonce: u64,
-    scoop: u32,
+    start_nonce: u64,
+    nonce_count: u64,
     poc_version: u8,
-    scoop_buffer: *mut u8,
-    scoop_buffer_offset: usize,
-) {
-    let offset_scoop_buffer = unsafe { scoop_buffer.add(scoop_buffer_offset) };
-    let mut buffer = [0u8; NONCE_SIZE];
-    pocc::plot::noncegen_single_rust(
-        &mut buffer,
-        account_id,
-        nonce,
-        poc_version,
-    );
-    let scoop_buffer_borrowed = unsafe { slice::from_raw_parts_mut(offset_scoop_buffer, SCOOP_SIZE) };
-    let offset = scoop as usize * SCOOP_SIZE;
-    scoop_buffer_borrowed.copy_from_slice(&buffer[offset..offset + SCOOP_SIZE]);
+    plot_buffer: *mut u8,
+    plot_buffer_len: usize,
+    plot_buffer_offset: usize,
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_poc_version(poc_version)?;
+        start_nonce.checked_add(nonce_count).ok_or(libshabal_status::INVALID_ARGUMENT)?;
+        let plot_size = (nonce_count as usize).checked_mul(NONCE_SIZE).ok_or(libshabal_status::INVALID_ARGUMENT)?;
+        status::check_buffer(plot_buffer, plot_buffer_len, plot_buffer_offset, plot_size)?;
+        let completed = unsafe {
+            let offset_plot_buffer = plot_buffer.add(plot_buffer_offset);
+            create_plots_with_callback(account_id, start_nonce, nonce_count, poc_version, offset_plot_buffer, cancelled, progress, progress_user_data)
+        };
+        return if completed { Ok(()) } else { Err(libshabal_status::CANCELLED) };
+    });
 }
 
-#[no_mangle]
-pub extern fn curve25519_get_public_key(private_key: *mut u8, public_key_buffer: *mut u8) {
-    unsafe {
-        let private_key_borrowed = slice::from_raw_parts_mut(private_key, 32);
-        let public_key_buffer_borrowed = slice::from_raw_parts_mut(public_key_buffer, 32);
-        curve25519::get_public_key(private_key_borrowed, public_key_buffer_borrowed)
+/// Number of nonces between progress reports and cancellation checks on the Rust side.
+/// The plotting kernels also poll the cancellation flag between each of their own batches.
+pub const PLOT_CHUNK_SIZE: u64 = 64;
+
+/// Creates `plot_buffer.len() / NONCE_SIZE` PoC Nonces starting at `start_nonce`, stopping early if `token` is cancelled.
+///
+/// `progress` receives the number of nonces completed so far after every chunk of `PLOT_CHUNK_SIZE` nonces.
+///
+/// Returns `true` if all nonces were created, or `false` if plotting was cancelled.
+pub fn create_plots_with_progress(
+    account_id: u64,
+    start_nonce: u64,
+    poc_version: u8,
+    plot_buffer: &mut [u8],
+    token: &CancellationToken,
+    progress: &mut dyn FnMut(u64),
+) -> bool {
+    let nonce_count = (plot_buffer.len() / NONCE_SIZE) as u64;
+    unsafe {
+        return create_plots_chunked(account_id, start_nonce, nonce_count, poc_version, plot_buffer.as_mut_ptr(), Some(token.flag()), progress);
     }
 }
 
-#[no_mangle]
-pub extern fn curve25519_get_shared_secret(private_key: *mut u8, public_key: *mut u8, shared_secret_buffer: *mut u8) {
-    unsafe {
-        let private_key_borrowed = slice::from_raw_parts_mut(private_key, 32);
-        let public_key_borrowed = slice::from_raw_parts_mut(public_key, 32);
-        let shared_secret_buffer_borrowed = slice::from_raw_parts_mut(shared_secret_buffer, 32);
-        curve25519::get_shared_secret(private_key_borrowed, public_key_borrowed, shared_secret_buffer_borrowed)
+#[allow(clippy::too_many_arguments)]
+unsafe fn create_plots_with_callback(
+    account_id: u64,
+    start_nonce: u64,
+    nonce_count: u64,
+    poc_version: u8,
+    plot_buffer: *mut u8,
+    cancelled: *const u8,
+    progress: ProgressCallback,
+    progress_user_data: *mut c_void,
+) -> bool {
+    let cancelled = (cancelled as *const AtomicU8).as_ref();
+    let mut report = |completed: u64| {
+        if let Some(progress) = progress { progress(completed, progress_user_data); }
+    };
+    return create_plots_chunked(account_id, start_nonce, nonce_count, poc_version, plot_buffer, cancelled, &mut report);
+}
+
+unsafe fn create_plots_chunked(
+    account_id: u64,
+    start_nonce: u64,
+    nonce_count: u64,
+    poc_version: u8,
+    plot_buffer: *mut u8,
+    cancelled: Option<&AtomicU8>,
+    progress: &mut dyn FnMut(u64),
+) -> bool {
+    let mut completed = 0;
+    while completed < nonce_count {
+        if cancel::is_set(cancelled) { return false; }
+        let chunk_size = PLOT_CHUNK_SIZE.min(nonce_count - completed);
+        create_plots_dispatch(
+            account_id,
+            start_nonce + completed,
+            chunk_size,
+            poc_version,
+            plot_buffer.add(completed as usize * NONCE_SIZE),
+            cancelled,
+        );
+        // the kernel may have stopped part way through the chunk
+        if cancel::is_set(cancelled) { return false; }
+        completed += chunk_size;
+        progress(completed);
     }
+    return true;
 }
 
-#[no_mangle]
-pub extern fn curve25519_sign(private_key: *mut u8, message_sha256: *const u8, signature_buffer: *mut u8) {
-    unsafe {
-        let private_key_borrowed = slice::from_raw_parts_mut(private_key, 32);
-        let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
-        let signature_buffer_borrowed = slice::from_raw_parts_mut(signature_buffer, 64);
-        curve25519::sign(private_key_borrowed, message_sha256_borrowed, signature_buffer_borrowed)
+unsafe fn create_plots_dispatch(
+    account_id: u64,
+    start_nonce: u64,
+    nonce_count: u64,
+    poc_version: u8,
+    plot_buffer: *mut u8,
+    cancelled: Option<&AtomicU8>,
+) {
+    init_kernels();
+    let supported_extension: &SimdExtension = &simd::SUPPORTED_SIMD_EXTENSION;
+    match supported_extension {
+        simd::SimdExtension::AVX512f => {
+            #[cfg(feature = "simd")]
+                noncegen_avx512(plot_buffer, account_id, start_nonce, nonce_count, poc_version, cancel::flag_ptr(cancelled));
+        },
+        simd::SimdExtension::AVX2 => {
+            #[cfg(feature = "simd")]
+                noncegen_avx2(plot_buffer, account_id, start_nonce, nonce_count, poc_version, cancel::flag_ptr(cancelled));
+        },
+        simd::SimdExtension::AVX => {
+            #[cfg(feature = "simd")]
+                noncegen_avx(plot_buffer, account_id, start_nonce, nonce_count, poc_version, cancel::flag_ptr(cancelled));
+        },
+        simd::SimdExtension::SSE2 => {
+            #[cfg(feature = "simd")]
+                noncegen_sse2(plot_buffer, account_id, start_nonce, nonce_count, poc_version, cancel::flag_ptr(cancelled));
+        },
+        _ => {
+            let plot_buffer_borrowed = slice::from_raw_parts_mut(plot_buffer, NONCE_SIZE * nonce_count as usize);
+            pocc::plot::noncegen_rust(plot_buffer_borrowed, account_id, start_nonce, nonce_count, poc_version, cancelled);
+        }
     }
 }
 
+/// Creates a single PoC Nonce.
+///
+/// `plot_buffer` must be correct size - no size checks are performed.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_plot(
+    account_id: u64,
+    nonce: u64,
+    poc_version: u8,
+    plot_buffer: *mut u8,
+    plot_buffer_offset: usize,
+) {
+    status::catch_panic((), || {
+        if plot_buffer.is_null() { return; }
+        unsafe { write_plot(account_id, nonce, poc_version, plot_buffer.add(plot_buffer_offset)); }
+    });
+}
+
+/// Checked variant of `create_plot`.
+///
+/// `plot_buffer_len` is the size of `plot_buffer` in bytes, which must have room for a nonce from
+/// `plot_buffer_offset`. `poc_version` must be 1 or 2.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_plot_checked(
+    account_id: u64,
+    nonce: u64,
+    poc_version: u8,
+    plot_buffer: *mut u8,
+    plot_buffer_len: usize,
+    plot_buffer_offset: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_poc_version(poc_version)?;
+        status::check_buffer(plot_buffer, plot_buffer_len, plot_buffer_offset, NONCE_SIZE)?;
+        unsafe { write_plot(account_id, nonce, poc_version, plot_buffer.add(plot_buffer_offset)); }
+        return Ok(());
+    });
+}
+
+unsafe fn write_plot(account_id: u64, nonce: u64, poc_version: u8, plot_buffer: *mut u8) {
+    pocc::plot::noncegen_single_rust(
+        slice::from_raw_parts_mut(plot_buffer, NONCE_SIZE),
+        account_id,
+        nonce,
+        poc_version,
+    );
+}
+
+/// Creates a single PoC Scoop.
+///
+/// `plot_buffer` must be correct size - no size checks are performed.
+///
+/// Does nothing if `scoop` is not below 4096.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_scoop(
+    account_id: u64,
+    nonce: u64,
+    scoop: u32,
+    poc_version: u8,
+    scoop_buffer: *mut u8,
+    scoop_buffer_offset: usize,
+) {
+    status::catch_panic((), || {
+        if scoop_buffer.is_null() || scoop as usize >= NUM_SCOOPS { return; }
+        unsafe { write_scoop(account_id, nonce, scoop, poc_version, scoop_buffer.add(scoop_buffer_offset)); }
+    });
+}
+
+/// Checked variant of `create_scoop`.
+///
+/// `scoop_buffer_len` is the size of `scoop_buffer` in bytes, which must have room for a scoop from
+/// `scoop_buffer_offset`. `scoop` must be below 4096 and `poc_version` must be 1 or 2.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn create_scoop_checked(
+    account_id: u64,
+    nonce: u64,
+    scoop: u32,
+    poc_version: u8,
+    scoop_buffer: *mut u8,
+    scoop_buffer_len: usize,
+    scoop_buffer_offset: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if scoop as usize >= NUM_SCOOPS { return Err(libshabal_status::INVALID_ARGUMENT); }
+        status::check_poc_version(poc_version)?;
+        status::check_buffer(scoop_buffer, scoop_buffer_len, scoop_buffer_offset, SCOOP_SIZE)?;
+        unsafe { write_scoop(account_id, nonce, scoop, poc_version, scoop_buffer.add(scoop_buffer_offset)); }
+        return Ok(());
+    });
+}
+
+unsafe fn write_scoop(account_id: u64, nonce: u64, scoop: u32, poc_version: u8, scoop_buffer: *mut u8) {
+    let mut buffer = vec![0u8; NONCE_SIZE];
+    pocc::plot::noncegen_single_rust(
+        &mut buffer,
+        account_id,
+        nonce,
+        poc_version,
+    );
+    let scoop_buffer_borrowed = slice::from_raw_parts_mut(scoop_buffer, SCOOP_SIZE);
+    let offset = scoop as usize * SCOOP_SIZE;
+    scoop_buffer_borrowed.copy_from_slice(&buffer[offset..offset + SCOOP_SIZE]);
+}
+
+/// Hashes a nonce-format message: `data_len` bytes of `data` followed by the seed of `account_id` and `nonce`.
+///
+/// Together with `shabal256_hash()` this reproduces the individual hashes of nonce generation,
+/// see the `nonce` module of the crate for the order they are generated in.
+///
+/// `out` must have 32 bytes available otherwise this will attempt to write beyond the array.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn nonce_seeded_hash(data: *const u8, data_len: usize, account_id: u64, nonce: u64, out: *mut u8) {
+    status::catch_panic((), || {
+        if (data.is_null() && data_len != 0) || out.is_null() { return; }
+        unsafe {
+            let out_borrowed = slice::from_raw_parts_mut(out, 32);
+            out_borrowed.copy_from_slice(&nonce::seeded_hash(input_slice(data, data_len), account_id, nonce));
+        }
+    });
+}
+
+/// Checked variant of `nonce_seeded_hash`.
+///
+/// `out_len` is the size of `out` in bytes, which must be at least 32.
+#[no_mangle]
+pub extern "C" fn nonce_seeded_hash_checked(
+    data: *const u8,
+    data_len: usize,
+    account_id: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if data_len != 0 { status::check_not_null(data)?; }
+        status::check_buffer(out, out_len, 0, 32)?;
+        nonce_seeded_hash(data, data_len, account_id, nonce, out);
+        return Ok(());
+    });
+}
+
+/// Hashes the whole 64 byte blocks of `data` closed by the 16 word termination block `termination`.
+///
+/// This is the Shabal256 primitive nonce generation is built on. `termination` holds the message bytes
+/// after the last whole block, the `0x80` padding byte and zeros, read as little endian words.
+/// Bytes of `data` after its last whole block are ignored.
+///
+/// `out` must have 32 bytes available otherwise this will attempt to write beyond the array.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn nonce_hash_with_termination(data: *const u8, data_len: usize, termination: *const u32, out: *mut u8) {
+    status::catch_panic((), || {
+        if (data.is_null() && data_len != 0) || termination.is_null() || out.is_null() { return; }
+        unsafe {
+            let termination_borrowed = &*(termination as *const [u32; 16]);
+            let out_borrowed = slice::from_raw_parts_mut(out, 32);
+            out_borrowed.copy_from_slice(&nonce::hash_with_termination(input_slice(data, data_len), termination_borrowed));
+        }
+    });
+}
+
+/// Checked variant of `nonce_hash_with_termination`.
+///
+/// `data_len` must be a multiple of 64 and `out_len`, the size of `out` in bytes, at least 32.
+#[no_mangle]
+pub extern "C" fn nonce_hash_with_termination_checked(
+    data: *const u8,
+    data_len: usize,
+    termination: *const u32,
+    out: *mut u8,
+    out_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if data_len != 0 { status::check_not_null(data)?; }
+        status::check_not_null(termination)?;
+        status::check_buffer(out, out_len, 0, 32)?;
+        if !data_len.is_multiple_of(64) { return Err(libshabal_status::INVALID_ARGUMENT); }
+        nonce_hash_with_termination(data, data_len, termination, out);
+        return Ok(());
+    });
+}
+
+/// Computes the Burst account id of a public key: the first 8 bytes of SHA-256(`public_key`), little endian.
+///
+/// `public_key` must be 32 bytes. Returns 0 if `public_key` is null.
+#[no_mangle]
+pub extern "C" fn account_id_from_public_key(public_key: *const u8) -> u64 {
+    return status::catch_panic(0, || {
+        if public_key.is_null() { return 0; }
+        unsafe { return account::account_id_from_public_key(&*(public_key as *const [u8; 32])); }
+    });
+}
+
+/// Checked variant of `account_id_from_public_key`.
+///
+/// `public_key_len` must be 32. The id is stored in `account_id`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn account_id_from_public_key_checked(public_key: *const u8, public_key_len: usize, account_id: *mut u64) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_not_null(account_id)?;
+        unsafe { *account_id = account::account_id_from_public_key(&*(public_key as *const [u8; 32])); }
+        return Ok(());
+    });
+}
+
+/// Computes the Burst account id of a passphrase.
+///
+/// The private key is SHA-256(`passphrase`), from which the public key and then the id are derived.
+/// `passphrase` is `passphrase_len` bytes, usually UTF-8. Returns 0 if `passphrase` is null.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn account_id_from_passphrase(passphrase: *const u8, passphrase_len: usize) -> u64 {
+    return status::catch_panic(0, || {
+        if passphrase.is_null() && passphrase_len != 0 { return 0; }
+        unsafe { return account::account_id_from_passphrase(input_slice(passphrase, passphrase_len)); }
+    });
+}
+
+/// Checked variant of `account_id_from_passphrase`.
+///
+/// The id is stored in `account_id`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn account_id_from_passphrase_checked(passphrase: *const u8, passphrase_len: usize, account_id: *mut u64) -> libshabal_status {
+    return status::run(|| {
+        if passphrase_len != 0 { status::check_not_null(passphrase)?; }
+        status::check_not_null(account_id)?;
+        unsafe { *account_id = account::account_id_from_passphrase(input_slice(passphrase, passphrase_len)); }
+        return Ok(());
+    });
+}
+
+/// Encodes an account id as a Reed-Solomon address, eg. `BURST-2222-2222-2222-22222`.
+///
+/// `prefix` is a null terminated string such as `BURST`, `S` or `TS`, or null for an address without prefix.
+/// The address is stored in `buffer` as a null terminated string, for which `buffer_len` must leave room -
+/// 21 bytes plus the length of the prefix and its `-` are always enough.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn burst_address_encode(account_id: u64, prefix: *const c_char, buffer: *mut c_char, buffer_len: usize) -> libshabal_status {
+    return status::run(|| {
+        let prefix = if prefix.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(prefix) }.to_str().map_err(|_| libshabal_status::INVALID_ARGUMENT)?)
+        };
+        let address = address::encode(account_id, prefix);
+        status::check_buffer(buffer as *const u8, buffer_len, 0, address.len() + 1)?;
+        unsafe {
+            let buffer_borrowed = slice::from_raw_parts_mut(buffer as *mut u8, address.len() + 1);
+            buffer_borrowed[..address.len()].copy_from_slice(address.as_bytes());
+            buffer_borrowed[address.len()] = 0;
+        }
+        return Ok(());
+    });
+}
+
+/// Decodes a null terminated Reed-Solomon address, with or without prefix, storing its id in `account_id`.
+///
+/// Returns `INVALID_ADDRESS` if the address is malformed or its checksum does not match.
+/// If `corrected` is not null it is set to 1 when the checksum did not match but the address is at most
+/// 2 symbols away from a valid one - `account_id` then holds the id of that address, which should be
+/// offered to the user rather than used directly. Otherwise it is set to 0.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn burst_address_decode(address: *const c_char, account_id: *mut u64, corrected: *mut u8) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(address)?;
+        status::check_not_null(account_id)?;
+        if !corrected.is_null() { unsafe { *corrected = 0; } }
+        let address = unsafe { CStr::from_ptr(address) }.to_str().map_err(|_| libshabal_status::INVALID_ADDRESS)?;
+        return match address::decode(address) {
+            Ok(id) => {
+                unsafe { *account_id = id; }
+                Ok(())
+            }
+            Err(address::AddressError::InvalidChecksum { corrected: Some(id) }) => {
+                unsafe {
+                    *account_id = id;
+                    if !corrected.is_null() { *corrected = 1; }
+                }
+                Err(libshabal_status::INVALID_ADDRESS)
+            }
+            Err(_) => Err(libshabal_status::INVALID_ADDRESS),
+        };
+    });
+}
+
+/// Derives the keys of a passphrase the way the Burst wallet does.
+///
+/// The private key is SHA-256(`passphrase`), which is clamped and used for key generation.
+/// Stores the public key in `public_key`, the EC-KCDSA signing key, for `curve25519_sign_with_signing_key()`,
+/// in `signing_key` and the clamped private key, for `curve25519_get_shared_secret()`, in `agreement_key`.
+/// `curve25519_sign()` with `agreement_key` gives the same signatures as the signing key.
+///
+/// Each output must have 32 bytes available otherwise this will attempt to write beyond the array.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn keypair_from_passphrase(
+    passphrase: *const u8,
+    passphrase_len: usize,
+    public_key: *mut u8,
+    signing_key: *mut u8,
+    agreement_key: *mut u8,
+) {
+    status::catch_panic((), || {
+        if (passphrase.is_null() && passphrase_len != 0) || public_key.is_null() || signing_key.is_null() || agreement_key.is_null() { return; }
+        unsafe { write_keypair(input_slice(passphrase, passphrase_len), public_key, signing_key, agreement_key); }
+    });
+}
+
+/// Checked variant of `keypair_from_passphrase`.
+///
+/// `public_key_len`, `signing_key_len` and `agreement_key_len` are the sizes of the outputs in bytes, which must be at least 32.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn keypair_from_passphrase_checked(
+    passphrase: *const u8,
+    passphrase_len: usize,
+    public_key: *mut u8,
+    public_key_len: usize,
+    signing_key: *mut u8,
+    signing_key_len: usize,
+    agreement_key: *mut u8,
+    agreement_key_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if passphrase_len != 0 { status::check_not_null(passphrase)?; }
+        status::check_buffer(public_key, public_key_len, 0, 32)?;
+        status::check_buffer(signing_key, signing_key_len, 0, 32)?;
+        status::check_buffer(agreement_key, agreement_key_len, 0, 32)?;
+        unsafe { write_keypair(input_slice(passphrase, passphrase_len), public_key, signing_key, agreement_key); }
+        return Ok(());
+    });
+}
+
+unsafe fn write_keypair(passphrase: &[u8], public_key: *mut u8, signing_key: *mut u8, agreement_key: *mut u8) {
+    let keypair = account::keypair_from_passphrase(passphrase);
+    slice::from_raw_parts_mut(public_key, 32).copy_from_slice(&keypair.public_key);
+    slice::from_raw_parts_mut(signing_key, 32).copy_from_slice(keypair.signing_key.as_bytes());
+    slice::from_raw_parts_mut(agreement_key, 32).copy_from_slice(keypair.agreement_key.as_bytes());
+}
+
+/// Computes the public key of a 32 byte private key.
+///
+/// The private key is clamped in a private copy, so the caller's key is left unchanged.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_get_public_key(private_key: *const u8, public_key_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if private_key.is_null() || public_key_buffer.is_null() { return; }
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let public_key_buffer_borrowed = slice::from_raw_parts_mut(public_key_buffer, 32);
+            curve25519::get_public_key(private_key_borrowed, public_key_buffer_borrowed)
+        }
+    });
+}
+
+/// Checked variant of `curve25519_get_public_key`.
+///
+/// `private_key_len` must be 32 and `public_key_buffer_len` at least 32.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_get_public_key_checked(
+    private_key: *const u8,
+    private_key_len: usize,
+    public_key_buffer: *mut u8,
+    public_key_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_buffer(public_key_buffer, public_key_buffer_len, 0, 32)?;
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let public_key_buffer_borrowed = slice::from_raw_parts_mut(public_key_buffer, 32);
+            curve25519::get_public_key(private_key_borrowed, public_key_buffer_borrowed);
+        }
+        return Ok(());
+    });
+}
+
+/// Computes the shared secret of a private key and another party's public key.
+///
+/// The private key is clamped in a private copy, so an unclamped key gives the same secret as its clamped form
+/// and neither key is modified.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_get_shared_secret(private_key: *const u8, public_key: *const u8, shared_secret_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if private_key.is_null() || public_key.is_null() || shared_secret_buffer.is_null() { return; }
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let public_key_borrowed = slice::from_raw_parts(public_key, 32);
+            let shared_secret_buffer_borrowed = slice::from_raw_parts_mut(shared_secret_buffer, 32);
+            curve25519::get_shared_secret(private_key_borrowed, public_key_borrowed, shared_secret_buffer_borrowed)
+        }
+    });
+}
+
+/// Checked variant of `curve25519_get_shared_secret`.
+///
+/// `private_key_len` and `public_key_len` must be 32 and `shared_secret_buffer_len` at least 32.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_get_shared_secret_checked(
+    private_key: *const u8,
+    private_key_len: usize,
+    public_key: *const u8,
+    public_key_len: usize,
+    shared_secret_buffer: *mut u8,
+    shared_secret_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_buffer(shared_secret_buffer, shared_secret_buffer_len, 0, 32)?;
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let public_key_borrowed = slice::from_raw_parts(public_key, 32);
+            let shared_secret_buffer_borrowed = slice::from_raw_parts_mut(shared_secret_buffer, 32);
+            curve25519::get_shared_secret(private_key_borrowed, public_key_borrowed, shared_secret_buffer_borrowed);
+        }
+        return Ok(());
+    });
+}
+
+/// Signs the SHA-256 hash of a message, storing the 64 byte signature in `signature_buffer`.
+///
+/// The private key is not modified.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign(private_key: *const u8, message_sha256: *const u8, signature_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if private_key.is_null() || message_sha256.is_null() || signature_buffer.is_null() { return; }
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            let signature_buffer_borrowed = slice::from_raw_parts_mut(signature_buffer, 64);
+            curve25519::sign(private_key_borrowed, message_sha256_borrowed, signature_buffer_borrowed)
+        }
+    });
+}
+
+/// Checked variant of `curve25519_sign`.
+///
+/// `private_key_len` and `message_sha256_len` must be 32 and `signature_buffer_len` at least 64.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign_checked(
+    private_key: *const u8,
+    private_key_len: usize,
+    message_sha256: *const u8,
+    message_sha256_len: usize,
+    signature_buffer: *mut u8,
+    signature_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_input(message_sha256, message_sha256_len, 32)?;
+        status::check_buffer(signature_buffer, signature_buffer_len, 0, 64)?;
+        unsafe {
+            let private_key_borrowed = slice::from_raw_parts(private_key, 32);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            let signature_buffer_borrowed = slice::from_raw_parts_mut(signature_buffer, 64);
+            curve25519::sign(private_key_borrowed, message_sha256_borrowed, signature_buffer_borrowed);
+        }
+        return Ok(());
+    });
+}
+
+/// Signs the SHA-256 hash of a message with the 32 byte signing key from `keypair_from_passphrase()`,
+/// storing the 64 byte signature in `signature_buffer`.
+///
+/// The signature is the one `curve25519_sign()` gives with the private key the signing key was derived from.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign_with_signing_key(signing_key: *const u8, message_sha256: *const u8, signature_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if signing_key.is_null() || message_sha256.is_null() || signature_buffer.is_null() { return; }
+        unsafe {
+            let signing_key_borrowed = slice::from_raw_parts(signing_key, 32);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            let signature_buffer_borrowed = slice::from_raw_parts_mut(signature_buffer, 64);
+            curve25519::sign_with_signing_key(signing_key_borrowed, message_sha256_borrowed, signature_buffer_borrowed)
+        }
+    });
+}
+
+/// Checked variant of `curve25519_sign_with_signing_key`.
+///
+/// `signing_key_len` and `message_sha256_len` must be 32 and `signature_buffer_len` at least 64.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign_with_signing_key_checked(
+    signing_key: *const u8,
+    signing_key_len: usize,
+    message_sha256: *const u8,
+    message_sha256_len: usize,
+    signature_buffer: *mut u8,
+    signature_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(signing_key, signing_key_len, 32)?;
+        status::check_input(message_sha256, message_sha256_len, 32)?;
+        status::check_buffer(signature_buffer, signature_buffer_len, 0, 64)?;
+        unsafe {
+            let signing_key_borrowed = slice::from_raw_parts(signing_key, 32);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            let signature_buffer_borrowed = slice::from_raw_parts_mut(signature_buffer, 64);
+            curve25519::sign_with_signing_key(signing_key_borrowed, message_sha256_borrowed, signature_buffer_borrowed);
+        }
+        return Ok(());
+    });
+}
+
 /// `enforce_canonical` and the return value are u8 bools where 0 represents false and 1 represents true.
 /// So, a return value of 1 indicates successful verification and a return value of 0 indicates unsuccessful verification.
 #[no_mangle]
-pub extern fn curve25519_verify(public_key: *mut u8, signature: *const u8, message_sha256: *const u8, enforce_canonical: u8) -> u8 {
-    unsafe {
-        let public_key_borrowed = slice::from_raw_parts_mut(public_key, 32);
-        let signature_borrowed = slice::from_raw_parts(signature, 64);
-        let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
-        return if curve25519::verify(public_key_borrowed, signature_borrowed, message_sha256_borrowed, enforce_canonical != 0) { 1 } else { 0 };
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify(public_key: *const u8, signature: *const u8, message_sha256: *const u8, enforce_canonical: u8) -> u8 {
+    return status::catch_panic(0, || {
+        if public_key.is_null() || signature.is_null() || message_sha256.is_null() { return 0; }
+        unsafe {
+            let public_key_borrowed = slice::from_raw_parts(public_key, 32);
+            let signature_borrowed = slice::from_raw_parts(signature, 64);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            return if curve25519::verify(public_key_borrowed, signature_borrowed, message_sha256_borrowed, enforce_canonical != 0) { 1 } else { 0 };
+        }
+    });
+}
+
+/// Checked variant of `curve25519_verify`.
+///
+/// `public_key_len` and `message_sha256_len` must be 32 and `signature_len` must be 64.
+///
+/// Returns `OK` if the signature is valid, `NON_CANONICAL` if `enforce_canonical` is non-zero and the public key
+/// or signature is not canonical, or `VERIFICATION_FAILED` if the signature does not match.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify_checked(
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    message_sha256: *const u8,
+    message_sha256_len: usize,
+    enforce_canonical: u8,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_input(signature, signature_len, 64)?;
+        status::check_input(message_sha256, message_sha256_len, 32)?;
+        unsafe {
+            let public_key_borrowed = slice::from_raw_parts(public_key, 32);
+            let signature_borrowed = slice::from_raw_parts(signature, 64);
+            let message_sha256_borrowed = slice::from_raw_parts(message_sha256, 32);
+            if enforce_canonical != 0 && !(curve25519::is_canonical_public_key(public_key_borrowed) && curve25519::is_canonical_signature(signature_borrowed)) {
+                return Err(libshabal_status::NON_CANONICAL);
+            }
+            return if curve25519::verify(public_key_borrowed, signature_borrowed, message_sha256_borrowed, false) {
+                Ok(())
+            } else {
+                Err(libshabal_status::VERIFICATION_FAILED)
+            };
+        }
+    });
+}
+
+/// Verifies `count` signatures in parallel, writing 1 to `results[i]` if signature `i` is valid and 0 if not.
+///
+/// `public_keys`, `signatures` and `message_hashes` are `count` consecutive 32, 64 and 32 byte items respectively.
+/// `enforce_canonical` applies to every item as in `curve25519_verify`.
+///
+/// Returns 1 if every signature is valid and 0 otherwise.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify_batch(
+    public_keys: *const u8,
+    signatures: *const u8,
+    message_hashes: *const u8,
+    count: usize,
+    enforce_canonical: u8,
+    results: *mut u8,
+) -> u8 {
+    return status::catch_panic(0, || {
+        if count == 0 { return 1; }
+        if public_keys.is_null() || signatures.is_null() || message_hashes.is_null() || results.is_null() { return 0; }
+        unsafe {
+            let results_borrowed = slice::from_raw_parts_mut(results, count);
+            return if verify_batch(public_keys, signatures, message_hashes, count, enforce_canonical, results_borrowed) { 1 } else { 0 };
+        }
+    });
+}
+
+/// Checked variant of `curve25519_verify_batch`.
+///
+/// `public_keys_len` and `message_hashes_len` must be `count * 32`, `signatures_len` must be `count * 64` and
+/// `results_len` at least `count`.
+///
+/// Returns `OK` if every signature is valid or `VERIFICATION_FAILED` if any is not, with `results` telling which.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify_batch_checked(
+    public_keys: *const u8,
+    public_keys_len: usize,
+    signatures: *const u8,
+    signatures_len: usize,
+    message_hashes: *const u8,
+    message_hashes_len: usize,
+    count: usize,
+    enforce_canonical: u8,
+    results: *mut u8,
+    results_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        let keys_len = count.checked_mul(32).ok_or(libshabal_status::INVALID_ARGUMENT)?;
+        status::check_input(public_keys, public_keys_len, keys_len)?;
+        status::check_input(signatures, signatures_len, count.checked_mul(64).ok_or(libshabal_status::INVALID_ARGUMENT)?)?;
+        status::check_input(message_hashes, message_hashes_len, keys_len)?;
+        status::check_buffer(results, results_len, 0, count)?;
+        unsafe {
+            let results_borrowed = slice::from_raw_parts_mut(results, count);
+            return if verify_batch(public_keys, signatures, message_hashes, count, enforce_canonical, results_borrowed) {
+                Ok(())
+            } else {
+                Err(libshabal_status::VERIFICATION_FAILED)
+            };
+        }
+    });
+}
+
+/// Runs `curve25519::verify_batch` on C arrays, returning whether every signature was valid.
+unsafe fn verify_batch(public_keys: *const u8, signatures: *const u8, message_hashes: *const u8, count: usize, enforce_canonical: u8, results: &mut [u8]) -> bool {
+    let public_keys = slice::from_raw_parts(public_keys as *const [u8; 32], count);
+    let signatures = slice::from_raw_parts(signatures as *const [u8; 64], count);
+    let message_hashes = slice::from_raw_parts(message_hashes as *const [u8; 32], count);
+    let mut valid = vec![false; count];
+    curve25519::verify_batch(public_keys, signatures, message_hashes, enforce_canonical != 0, &mut valid);
+    for (result, &valid) in results.iter_mut().zip(valid.iter()) {
+        *result = valid as u8;
+    }
+    return valid.iter().all(|&valid| valid);
+}
+
+/// Returns 1 if the first half of the 64 byte `signature` is reduced modulo the group order and 0 if not.
+///
+/// Nodes only accept canonical signatures, so non-canonical ones can be rejected before verifying them.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_is_canonical_signature(signature: *const u8) -> u8 {
+    return status::catch_panic(0, || {
+        if signature.is_null() { return 0; }
+        unsafe { return curve25519::is_canonical_signature(slice::from_raw_parts(signature, 64)) as u8; }
+    });
+}
+
+/// Checked variant of `curve25519_is_canonical_signature`.
+///
+/// `signature_len` must be 64. Returns `OK` if the signature is canonical and `NON_CANONICAL` if not.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_is_canonical_signature_checked(signature: *const u8, signature_len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(signature, signature_len, 64)?;
+        let canonical = unsafe { curve25519::is_canonical_signature(slice::from_raw_parts(signature, 64)) };
+        return if canonical { Ok(()) } else { Err(libshabal_status::NON_CANONICAL) };
+    });
+}
+
+/// Returns 1 if the 32 byte `public_key` is reduced modulo 2^255 - 19 and 0 if not.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_is_canonical_public_key(public_key: *const u8) -> u8 {
+    return status::catch_panic(0, || {
+        if public_key.is_null() { return 0; }
+        unsafe { return curve25519::is_canonical_public_key(slice::from_raw_parts(public_key, 32)) as u8; }
+    });
+}
+
+/// Checked variant of `curve25519_is_canonical_public_key`.
+///
+/// `public_key_len` must be 32. Returns `OK` if the public key is canonical and `NON_CANONICAL` if not.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_is_canonical_public_key_checked(public_key: *const u8, public_key_len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(public_key, public_key_len, 32)?;
+        let canonical = unsafe { curve25519::is_canonical_public_key(slice::from_raw_parts(public_key, 32)) };
+        return if canonical { Ok(()) } else { Err(libshabal_status::NON_CANONICAL) };
+    });
+}
+
+/// Stores the canonical form of the 64 byte `signature` in `canonical_buffer`, which may be `signature` itself.
+///
+/// The canonical signature verifies as the original does for any public key from `curve25519_get_public_key()`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_canonicalize_signature(signature: *const u8, canonical_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if signature.is_null() || canonical_buffer.is_null() { return; }
+        unsafe { write_canonical(signature, canonical_buffer, 64, curve25519::canonicalize_signature); }
+    });
+}
+
+/// Checked variant of `curve25519_canonicalize_signature`.
+///
+/// `signature_len` must be 64 and `canonical_buffer_len` at least 64.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_canonicalize_signature_checked(signature: *const u8, signature_len: usize, canonical_buffer: *mut u8, canonical_buffer_len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(signature, signature_len, 64)?;
+        status::check_buffer(canonical_buffer, canonical_buffer_len, 0, 64)?;
+        unsafe { write_canonical(signature, canonical_buffer, 64, curve25519::canonicalize_signature); }
+        return Ok(());
+    });
+}
+
+/// Stores the canonical form of the 32 byte `public_key` in `canonical_buffer`, which may be `public_key` itself.
+///
+/// The canonical public key verifies the same signatures, but hashes to a different account id.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_canonicalize_public_key(public_key: *const u8, canonical_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if public_key.is_null() || canonical_buffer.is_null() { return; }
+        unsafe { write_canonical(public_key, canonical_buffer, 32, curve25519::canonicalize_public_key); }
+    });
+}
+
+/// Checked variant of `curve25519_canonicalize_public_key`.
+///
+/// `public_key_len` must be 32 and `canonical_buffer_len` at least 32.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_canonicalize_public_key_checked(public_key: *const u8, public_key_len: usize, canonical_buffer: *mut u8, canonical_buffer_len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_buffer(canonical_buffer, canonical_buffer_len, 0, 32)?;
+        unsafe { write_canonical(public_key, canonical_buffer, 32, curve25519::canonicalize_public_key); }
+        return Ok(());
+    });
+}
+
+/// Runs `canonicalize` on a copy of the `len` byte input, so the output may overlap it.
+unsafe fn write_canonical(input: *const u8, canonical_buffer: *mut u8, len: usize, canonicalize: fn(&[u8], &mut [u8])) {
+    let input = slice::from_raw_parts(input, len).to_vec();
+    canonicalize(&input, slice::from_raw_parts_mut(canonical_buffer, len));
+}
+
+/// Signs `message_len` bytes of `message`, hashing them with SHA-256, and stores the 64 byte signature in `signature_buffer`.
+///
+/// The same as `curve25519_sign()` with the SHA-256 hash of the message. The private key is not modified.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign_message(private_key: *const u8, message: *const u8, message_len: usize, signature_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if private_key.is_null() || (message.is_null() && message_len != 0) || signature_buffer.is_null() { return; }
+        unsafe { write_message_signature(private_key, message, message_len, signature_buffer); }
+    });
+}
+
+/// Checked variant of `curve25519_sign_message`.
+///
+/// `private_key_len` must be 32 and `signature_buffer_len` at least 64.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_sign_message_checked(
+    private_key: *const u8,
+    private_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    signature_buffer: *mut u8,
+    signature_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(private_key, private_key_len, 32)?;
+        if message_len != 0 { status::check_not_null(message)?; }
+        status::check_buffer(signature_buffer, signature_buffer_len, 0, 64)?;
+        unsafe { write_message_signature(private_key, message, message_len, signature_buffer); }
+        return Ok(());
+    });
+}
+
+unsafe fn write_message_signature(private_key: *const u8, message: *const u8, message_len: usize, signature_buffer: *mut u8) {
+    let mut hasher = MessageHasher::new();
+    hasher.update(input_slice(message, message_len));
+    sign_hashed_message(hasher, private_key, signature_buffer);
+}
+
+/// Verifies the signature of `message_len` bytes of `message`, hashing them with SHA-256.
+///
+/// The same as `curve25519_verify()` with the SHA-256 hash of the message, returning 1 if the signature is valid and 0 if not.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify_message(public_key: *const u8, signature: *const u8, message: *const u8, message_len: usize, enforce_canonical: u8) -> u8 {
+    return status::catch_panic(0, || {
+        if public_key.is_null() || signature.is_null() || (message.is_null() && message_len != 0) { return 0; }
+        unsafe {
+            let mut hasher = MessageHasher::new();
+            hasher.update(input_slice(message, message_len));
+            return verify_hashed_message(hasher, public_key, signature, enforce_canonical).is_ok() as u8;
+        }
+    });
+}
+
+/// Checked variant of `curve25519_verify_message`.
+///
+/// `public_key_len` must be 32 and `signature_len` must be 64. The statuses are those of `curve25519_verify_checked()`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_verify_message_checked(
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    message: *const u8,
+    message_len: usize,
+    enforce_canonical: u8,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_input(signature, signature_len, 64)?;
+        if message_len != 0 { status::check_not_null(message)?; }
+        unsafe {
+            let mut hasher = MessageHasher::new();
+            hasher.update(input_slice(message, message_len));
+            return verify_hashed_message(hasher, public_key, signature, enforce_canonical);
+        }
+    });
+}
+
+/// Create a message hasher for signing or verifying a message which arrives in pieces.
+///
+/// Returns a pointer to the instance. Add the message with `curve25519_message_update()`, then pass the instance
+/// to `curve25519_message_sign()` or `curve25519_message_verify()` and release it with `curve25519_message_destroy()`.
+#[no_mangle]
+pub extern "C" fn curve25519_message_new() -> *mut c_void {
+    return status::catch_panic(null_mut(), || Box::into_raw(Box::new(MessageHasher::new())) as *mut c_void);
+}
+
+/// Destroy a message hasher returned from `curve25519_message_new()`.
+#[no_mangle]
+pub extern "C" fn curve25519_message_destroy(hasher: *mut c_void) {
+    status::catch_panic((), || {
+        if hasher.is_null() { return; }
+        unsafe { drop(Box::from_raw(hasher as *mut MessageHasher)); }
+    });
+}
+
+/// Add the next piece of the message, `len` bytes of `data` starting at `offset`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_update(hasher: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    status::catch_panic((), || {
+        if hasher.is_null() || data.is_null() { return; }
+        unsafe { message_hasher(hasher).update(slice::from_raw_parts(data.add(offset), len)); }
+    });
+}
+
+/// Checked variant of `curve25519_message_update`.
+///
+/// `data_len` is the size of `data` in bytes, which must contain `len` bytes from `offset`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_update_checked(hasher: *mut c_void, data: *const u8, data_len: usize, offset: usize, len: usize) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(hasher)?;
+        status::check_buffer(data, data_len, offset, len)?;
+        unsafe { message_hasher(hasher).update(slice::from_raw_parts(data.add(offset), len)); }
+        return Ok(());
+    });
+}
+
+/// Signs the message added to `hasher` and resets it, storing the 64 byte signature in `signature_buffer`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_sign(hasher: *mut c_void, private_key: *const u8, signature_buffer: *mut u8) {
+    status::catch_panic((), || {
+        if hasher.is_null() || private_key.is_null() || signature_buffer.is_null() { return; }
+        unsafe { sign_hashed_message(take_message(hasher), private_key, signature_buffer); }
+    });
+}
+
+/// Checked variant of `curve25519_message_sign`.
+///
+/// `private_key_len` must be 32 and `signature_buffer_len` at least 64.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_sign_checked(
+    hasher: *mut c_void,
+    private_key: *const u8,
+    private_key_len: usize,
+    signature_buffer: *mut u8,
+    signature_buffer_len: usize,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(hasher)?;
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_buffer(signature_buffer, signature_buffer_len, 0, 64)?;
+        unsafe { sign_hashed_message(take_message(hasher), private_key, signature_buffer); }
+        return Ok(());
+    });
+}
+
+/// Verifies the signature of the message added to `hasher` and resets it.
+///
+/// Returns 1 if the signature is valid and 0 if not, as `curve25519_verify()`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_verify(hasher: *mut c_void, public_key: *const u8, signature: *const u8, enforce_canonical: u8) -> u8 {
+    return status::catch_panic(0, || {
+        if hasher.is_null() || public_key.is_null() || signature.is_null() { return 0; }
+        unsafe { return verify_hashed_message(take_message(hasher), public_key, signature, enforce_canonical).is_ok() as u8; }
+    });
+}
+
+/// Checked variant of `curve25519_message_verify`.
+///
+/// `public_key_len` must be 32 and `signature_len` must be 64. The statuses are those of `curve25519_verify_checked()`.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn curve25519_message_verify_checked(
+    hasher: *mut c_void,
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    enforce_canonical: u8,
+) -> libshabal_status {
+    return status::run(|| {
+        status::check_not_null(hasher)?;
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_input(signature, signature_len, 64)?;
+        unsafe { return verify_hashed_message(take_message(hasher), public_key, signature, enforce_canonical); }
+    });
+}
+
+/// Copies a 32 byte private key into a `PrivateKey`, which is wiped when dropped.
+unsafe fn private_key_from_ptr(private_key: *const u8) -> PrivateKey {
+    return PrivateKey::from_bytes(&*(private_key as *const [u8; 32]));
+}
+
+unsafe fn message_hasher<'a>(hasher: *mut c_void) -> &'a mut MessageHasher {
+    return &mut *(hasher as *mut MessageHasher);
+}
+
+/// Takes the message out of `hasher`, leaving it reset.
+unsafe fn take_message(hasher: *mut c_void) -> MessageHasher {
+    return std::mem::take(message_hasher(hasher));
+}
+
+unsafe fn sign_hashed_message(hasher: MessageHasher, private_key: *const u8, signature_buffer: *mut u8) {
+    let private_key = private_key_from_ptr(private_key);
+    slice::from_raw_parts_mut(signature_buffer, 64).copy_from_slice(&hasher.sign(&private_key));
+}
+
+/// Verifies as `curve25519_verify_checked()` does, telling a non-canonical input from a failed verification.
+unsafe fn verify_hashed_message(hasher: MessageHasher, public_key: *const u8, signature: *const u8, enforce_canonical: u8) -> Result<(), libshabal_status> {
+    let public_key_borrowed = &*(public_key as *const [u8; 32]);
+    let signature_borrowed = &*(signature as *const [u8; 64]);
+    if enforce_canonical != 0 && !(curve25519::is_canonical_public_key(public_key_borrowed) && curve25519::is_canonical_signature(signature_borrowed)) {
+        return Err(libshabal_status::NON_CANONICAL);
+    }
+    return if hasher.verify(public_key_borrowed, signature_borrowed, false) {
+        Ok(())
+    } else {
+        Err(libshabal_status::VERIFICATION_FAILED)
+    };
+}
+
+/// Encrypts `plaintext_len` bytes of `plaintext` from the owner of `private_key` to the owner of `public_key`,
+/// as a Burst encrypted message. To encrypt to self pass the public key of `private_key`.
+///
+/// `private_key_len` and `public_key_len` must be 32 and `nonce_len` at least 32. The encrypted data is stored in `data`
+/// and its length in `data_written`. If `data_len` is too small `BUFFER_TOO_SMALL` is returned with the length needed in
+/// `data_written` - it only depends on the plaintext, so the call can be repeated with a large enough buffer.
+/// `data` may be null with a `data_len` of 0 to just get the length.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn burst_encrypt_data(
+    plaintext: *const u8,
+    plaintext_len: usize,
+    private_key: *const u8,
+    private_key_len: usize,
+    public_key: *const u8,
+    public_key_len: usize,
+    nonce: *mut u8,
+    nonce_len: usize,
+    data: *mut u8,
+    data_len: usize,
+    data_written: *mut usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if plaintext_len != 0 { status::check_not_null(plaintext)?; }
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_buffer(nonce, nonce_len, 0, 32)?;
+        status::check_not_null(data_written)?;
+        unsafe {
+            let private_key = private_key_from_ptr(private_key);
+            let encrypted = encrypted_data::encrypt_data(input_slice(plaintext, plaintext_len), &private_key, &*(public_key as *const [u8; 32]));
+            *data_written = encrypted.data.len();
+            if data_len < encrypted.data.len() { return Err(libshabal_status::BUFFER_TOO_SMALL); }
+            status::check_buffer(data, data_len, 0, encrypted.data.len())?;
+            slice::from_raw_parts_mut(data, encrypted.data.len()).copy_from_slice(&encrypted.data);
+            slice::from_raw_parts_mut(nonce, 32).copy_from_slice(&encrypted.nonce);
+        }
+        return Ok(());
+    });
+}
+
+/// Decrypts a Burst encrypted message between the owner of `private_key` and the owner of `public_key`, whichever sent it.
+///
+/// `private_key_len`, `public_key_len` and `nonce_len` must be 32. The plaintext is stored in `plaintext` and its length in
+/// `plaintext_written`. If `plaintext_len` is too small `BUFFER_TOO_SMALL` is returned with the length needed in `plaintext_written`,
+/// so `plaintext` may be null with a `plaintext_len` of 0 to just get the length.
+///
+/// Returns `DECRYPTION_FAILED` if the data is malformed or the keys or nonce are wrong.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn burst_decrypt_data(
+    data: *const u8,
+    data_len: usize,
+    nonce: *const u8,
+    nonce_len: usize,
+    private_key: *const u8,
+    private_key_len: usize,
+    public_key: *const u8,
+    public_key_len: usize,
+    plaintext: *mut u8,
+    plaintext_len: usize,
+    plaintext_written: *mut usize,
+) -> libshabal_status {
+    return status::run(|| {
+        if data_len != 0 { status::check_not_null(data)?; }
+        status::check_input(nonce, nonce_len, 32)?;
+        status::check_input(private_key, private_key_len, 32)?;
+        status::check_input(public_key, public_key_len, 32)?;
+        status::check_not_null(plaintext_written)?;
+        unsafe {
+            let private_key = private_key_from_ptr(private_key);
+            let encrypted = EncryptedData { data: input_slice(data, data_len).to_vec(), nonce: *(nonce as *const [u8; 32]) };
+            let decrypted = Zeroizing::new(
+                encrypted_data::decrypt_data(&encrypted, &private_key, &*(public_key as *const [u8; 32]))
+                    .map_err(|_| libshabal_status::DECRYPTION_FAILED)?,
+            );
+            *plaintext_written = decrypted.len();
+            if plaintext_len < decrypted.len() { return Err(libshabal_status::BUFFER_TOO_SMALL); }
+            if decrypted.is_empty() { return Ok(()); }
+            status::check_buffer(plaintext, plaintext_len, 0, decrypted.len())?;
+            slice::from_raw_parts_mut(plaintext, decrypted.len()).copy_from_slice(&decrypted);
+        }
+        return Ok(());
+    });
+}
+
+/// Builds and signs the transaction making `pool_account_id` the reward recipient of the account of `passphrase`,
+/// storing its bytes in `transaction_bytes` and its id in `transaction_id`. Nothing is broadcast.
+///
+/// `timestamp` is in seconds since the Burst epoch, or 0 for the current time. `ec_block_height` and `ec_block_id`
+/// are a recent block of the chain, as returned by a node's `getECBlock`.
+/// `transaction_bytes_len` must be at least `REWARD_RECIPIENT_ASSIGNMENT_SIZE` (177).
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn burst_reward_recipient_assignment(
+    passphrase: *const u8,
+    passphrase_len: usize,
+    pool_account_id: u64,
+    fee_nqt: u64,
+    timestamp: u32,
+    ec_block_height: u32,
+    ec_block_id: u64,
+    transaction_bytes: *mut u8,
+    transaction_bytes_len: usize,
+    transaction_id: *mut u64,
+) -> libshabal_status {
+    return status::run(|| {
+        if passphrase_len != 0 { status::check_not_null(passphrase)?; }
+        status::check_buffer(transaction_bytes, transaction_bytes_len, 0, transaction::REWARD_RECIPIENT_ASSIGNMENT_SIZE)?;
+        status::check_not_null(transaction_id)?;
+        let timestamp = if timestamp == 0 { transaction::current_timestamp() } else { timestamp };
+        unsafe {
+            let transaction = transaction::assign_reward_recipient(input_slice(passphrase, passphrase_len), pool_account_id, fee_nqt, timestamp, ec_block_height, ec_block_id);
+            let bytes = transaction.to_bytes();
+            slice::from_raw_parts_mut(transaction_bytes, bytes.len()).copy_from_slice(&bytes);
+            *transaction_id = transaction.id().ok_or(libshabal_status::INTERNAL_ERROR)?;
+        }
+        return Ok(());
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ptr::null_mut;
+
+    fn test_scoops(nonce_count: usize) -> Vec<u8> {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        return (0..nonce_count * SCOOP_SIZE).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        }).collect();
+    }
+
+    #[test]
+    fn find_best_deadline_matches_direct() {
+        let nonce_count = DEADLINE_CHUNK_SIZE * 2 + 5;
+        let scoops = test_scoops(nonce_count as usize);
+        let gensig = [7u8; 32];
+
+        let mut expected_deadline = u64::MAX;
+        let mut expected_offset = 0;
+        shabal_findBestDeadlineDirect(scoops.as_ptr(), nonce_count, gensig.as_ptr(), &mut expected_deadline, &mut expected_offset);
+
+        let mut reports = Vec::new();
+        let result = find_best_deadline(&scoops, &gensig, &CancellationToken::new(), &mut |processed| reports.push(processed));
+        assert_eq!(result, Some((expected_deadline, expected_offset)));
+        assert_eq!(reports, vec![DEADLINE_CHUNK_SIZE, DEADLINE_CHUNK_SIZE * 2, nonce_count]);
+    }
+
+    #[test]
+    fn find_best_deadline_cancelled() {
+        let scoops = test_scoops(64);
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut reports = 0;
+        assert_eq!(find_best_deadline(&scoops, &[0u8; 32], &token, &mut |_| reports += 1), None);
+        assert_eq!(reports, 0);
+
+        let mut deadline = 42;
+        let mut offset = 42;
+        // any non-zero byte cancels, not only 1
+        for cancelled in [1u8, 2, 0xFF] {
+            let completed = shabal_findBestDeadlineCancellable(scoops.as_ptr(), 64, [0u8; 32].as_ptr(), &mut deadline, &mut offset, &cancelled, None, null_mut());
+            assert_eq!(completed, 0);
+            assert_eq!((deadline, offset), (42, 42));
+        }
+        assert_eq!(
+            shabal_findBestDeadlineChecked(scoops.as_ptr(), scoops.len(), 64, [0u8; 32].as_ptr(), 32, &mut deadline, &mut offset, &2, None, null_mut()),
+            libshabal_status::CANCELLED,
+        );
+    }
+
+    #[test]
+    fn create_plots_with_progress_matches_create_plot() {
+        let mut plot_buffer = vec![0u8; NONCE_SIZE * 2];
+        let mut reports = Vec::new();
+        assert!(create_plots_with_progress(1234, 10, 2, &mut plot_buffer, &CancellationToken::new(), &mut |completed| reports.push(completed)));
+        assert_eq!(reports, vec![2]);
+
+        let mut expected = vec![0u8; NONCE_SIZE * 2];
+        create_plot(1234, 10, 2, expected.as_mut_ptr(), 0);
+        create_plot(1234, 11, 2, expected.as_mut_ptr(), NONCE_SIZE);
+        assert!(plot_buffer == expected);
+    }
+
+    #[test]
+    fn create_plots_cancelled() {
+        let mut plot_buffer = vec![0u8; NONCE_SIZE];
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut reports = 0;
+        assert!(!create_plots_with_progress(1234, 10, 2, &mut plot_buffer, &token, &mut |_| reports += 1));
+        assert_eq!(reports, 0);
+
+        // any non-zero byte cancels, not only 1
+        for cancelled in [1u8, 2, 0xFF] {
+            let completed = create_plots_cancellable(1234, 10, 1, 2, plot_buffer.as_mut_ptr(), 0, &cancelled, None, null_mut());
+            assert_eq!(completed, 0);
+        }
+        assert_eq!(create_plots_checked(1234, 10, 1, 2, plot_buffer.as_mut_ptr(), NONCE_SIZE, 0, &2, None, null_mut()), libshabal_status::CANCELLED);
+        assert!(plot_buffer.iter().all(|&byte| byte == 0));
+
+        // and the Rust plotter stops on it too
+        let cancelled = AtomicU8::new(2);
+        pocc::plot::noncegen_rust(&mut plot_buffer, 1234, 10, 1, 2, Some(&cancelled));
+        assert!(plot_buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn checked_functions_reject_invalid_input() {
+        let mut scoop_buffer = [0u8; SCOOP_SIZE];
+        assert_eq!(create_scoop_checked(1234, 10, 4096, 2, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 0), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(create_scoop_checked(1234, 10, 0, 3, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 0), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(create_scoop_checked(1234, 10, 0, 2, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 1), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(create_scoop_checked(1234, 10, 0, 2, null_mut(), SCOOP_SIZE, 0), libshabal_status::NULL_POINTER);
+        assert_eq!(create_plot_checked(1234, 10, 2, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 0), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(create_plots_checked(1234, 10, u64::MAX, 2, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 0, null(), None, null_mut()), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(shabal256_update_checked(null_mut(), scoop_buffer.as_ptr(), SCOOP_SIZE, 0, 1), libshabal_status::NULL_POINTER);
+
+        let shabal = shabal256_new();
+        assert_eq!(shabal256_update_checked(shabal, scoop_buffer.as_ptr(), SCOOP_SIZE, usize::MAX, 2), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(shabal256_digest_checked(shabal, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 33), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(shabal256_digest_checked(shabal, scoop_buffer.as_mut_ptr(), SCOOP_SIZE, 32), libshabal_status::OK);
+        shabal256_destroy(shabal);
+
+        let mut deadline = 0;
+        let mut offset = 0;
+        assert_eq!(shabal_findBestDeadlineChecked(scoop_buffer.as_ptr(), SCOOP_SIZE, 2, [0u8; 32].as_ptr(), 32, &mut deadline, &mut offset, null(), None, null_mut()), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(shabal_findBestDeadlineChecked(scoop_buffer.as_ptr(), SCOOP_SIZE, 1, [0u8; 32].as_ptr(), 31, &mut deadline, &mut offset, null(), None, null_mut()), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(shabal_findBestDeadlineChecked(scoop_buffer.as_ptr(), SCOOP_SIZE, 1, [0u8; 32].as_ptr(), 32, &mut deadline, &mut offset, null(), None, null_mut()), libshabal_status::OK);
+    }
+
+    #[test]
+    fn curve25519_verify_checked_statuses() {
+        let mut private_key = [0x42u8; 32];
+        let mut public_key = [0u8; 32];
+        let message_sha256 = [0x13u8; 32];
+        let mut signature = [0u8; 64];
+        assert_eq!(curve25519_get_public_key_checked(private_key.as_mut_ptr(), 32, public_key.as_mut_ptr(), 32), libshabal_status::OK);
+        assert_eq!(curve25519_sign_checked(private_key.as_mut_ptr(), 32, message_sha256.as_ptr(), 32, signature.as_mut_ptr(), 63), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(curve25519_sign_checked(private_key.as_mut_ptr(), 32, message_sha256.as_ptr(), 32, signature.as_mut_ptr(), 64), libshabal_status::OK);
+
+        assert_eq!(curve25519_verify_checked(public_key.as_ptr(), 32, signature.as_ptr(), 64, message_sha256.as_ptr(), 32, 1), libshabal_status::OK);
+        assert_eq!(curve25519_verify_checked(public_key.as_ptr(), 32, signature.as_ptr(), 63, message_sha256.as_ptr(), 32, 1), libshabal_status::INVALID_ARGUMENT);
+        signature[40] ^= 1;
+        assert_eq!(curve25519_verify_checked(public_key.as_ptr(), 32, signature.as_ptr(), 64, message_sha256.as_ptr(), 32, 0), libshabal_status::VERIFICATION_FAILED);
+    }
+
+    #[test]
+    fn status_strings_are_null_terminated() {
+        let description = |status| unsafe { std::ffi::CStr::from_ptr(libshabal_status_string(status) as *const std::os::raw::c_char) };
+        assert_eq!(description(libshabal_status::BUFFER_TOO_SMALL as u32).to_str().unwrap(), "A buffer was too small for the requested operation");
+        assert_eq!(description(libshabal_status::DECRYPTION_FAILED as u32).to_str().unwrap(), libshabal_status::DECRYPTION_FAILED.description().trim_end_matches('\0'));
+        assert_eq!(description(10).to_str().unwrap(), "Unknown status");
+        assert_eq!(description(u32::MAX).to_str().unwrap(), "Unknown status");
+    }
+
+    #[test]
+    fn statuses_round_trip_through_their_values() {
+        for value in 0..=9 {
+            assert_eq!(libshabal_status::from_u32(value).map(|status| status as u32), Some(value));
+        }
+        assert_eq!(libshabal_status::from_u32(10), None);
+    }
+
+    #[test]
+    fn invalid_input_does_not_unwind_into_caller() {
+        let mut scoop_buffer = [0xAAu8; SCOOP_SIZE];
+        create_scoop(1234, 10, 4096, 2, scoop_buffer.as_mut_ptr(), 0);
+        create_scoop(1234, 10, u32::MAX, 2, scoop_buffer.as_mut_ptr(), 0);
+        assert!(scoop_buffer.iter().all(|&byte| byte == 0xAA));
+
+        create_scoop(1234, 10, 0, 2, null_mut(), 0);
+        create_plot(1234, 10, 2, null_mut(), 0);
+        create_plots(1234, 10, 1, 2, null_mut(), 0);
+        assert_eq!(create_plots_cancellable(1234, 10, 1, 2, null_mut(), 0, null(), None, null_mut()), 0);
+
+        let mut deadline = 42;
+        let mut offset = 42;
+        shabal_findBestDeadlineDirect(null(), 1, null(), &mut deadline, &mut offset);
+        assert_eq!((deadline, offset), (42, 42));
+        assert_eq!(shabal_findBestDeadline(null(), 1, null()), 0);
+        assert_eq!(shabal_findBestDeadlineCancellable(null(), 1, null(), &mut deadline, &mut offset, null(), None, null_mut()), 0);
+
+        shabal256_reset(null_mut());
+        shabal256_update(null_mut(), null(), 0, 1);
+        shabal256_digest(null_mut(), null_mut(), 0);
+        shabal256_destroy(null_mut());
+        let shabal = shabal256_new();
+        shabal256_update(shabal, null(), 0, 1);
+        shabal256_digest(shabal, null_mut(), 0);
+        shabal256_destroy(shabal);
+
+        curve25519_get_public_key(null_mut(), null_mut());
+        curve25519_get_shared_secret(null_mut(), null_mut(), null_mut());
+        curve25519_sign(null_mut(), null(), null_mut());
+        assert_eq!(curve25519_verify(null_mut(), null(), null(), 1), 0);
+    }
+
+    #[test]
+    fn panics_are_converted_to_status() {
+        assert_eq!(status::run(|| panic!("test panic")), libshabal_status::INTERNAL_ERROR);
+        assert_eq!(status::catch_panic(7, || -> u8 { panic!("test panic") }), 7);
+    }
+
+    #[test]
+    fn kernels_work_without_shabal_init() {
+        // 17 nonces covers both the widest SIMD batch and the scalar tail
+        let nonce_count: u64 = if cfg!(feature = "simd") { 17 } else { 2 };
+
+        let scoops = test_scoops(nonce_count as usize);
+        let gensig = [3u8; 32];
+        let mut deadline = u64::MAX;
+        let mut offset = 0;
+        let mut expected_deadline = u64::MAX;
+        let mut expected_offset = 0;
+        shabal_findBestDeadlineDirect(scoops.as_ptr(), nonce_count, gensig.as_ptr(), &mut deadline, &mut offset);
+        unsafe { find_best_deadline_sph(scoops.as_ptr(), nonce_count, gensig.as_ptr(), &mut expected_deadline, &mut expected_offset, null()); }
+        assert_eq!((deadline, offset), (expected_deadline, expected_offset));
+
+        let mut plot_buffer = vec![0u8; NONCE_SIZE * nonce_count as usize];
+        create_plots(1234, 10, nonce_count, 2, plot_buffer.as_mut_ptr(), 0);
+        let mut expected = vec![0u8; NONCE_SIZE];
+        for n in 0..nonce_count as usize {
+            pocc::plot::noncegen_single_rust(&mut expected, 1234, 10 + n as u64, 2);
+            assert!(plot_buffer[n * NONCE_SIZE..(n + 1) * NONCE_SIZE] == expected[..]);
+        }
+    }
+
+    #[test]
+    fn shabal512_streaming_matches_digest() {
+        let message = b"The quick brown fox jumps over the lazy dog";
+        let shabal = shabal512_new();
+        shabal512_update(shabal, message.as_ptr(), 0, 10);
+        assert_eq!(shabal512_update_checked(shabal, message.as_ptr(), message.len(), 10, message.len() - 10), libshabal_status::OK);
+        let mut result = [0u8; 65];
+        assert_eq!(shabal512_digest_checked(shabal, result.as_mut_ptr(), result.len(), 2), libshabal_status::BUFFER_TOO_SMALL);
+        shabal512_digest(shabal, result.as_mut_ptr(), 1);
+        shabal512_destroy(shabal);
+        assert_eq!(result[1..].to_vec(), <shabal::Shabal512 as shabal::Digest>::digest(message).to_vec());
+    }
+
+    #[test]
+    fn shabal256_hash_many_matches_single_hashes() {
+        // mixes batches wider than any SIMD kernel with lengths that only occur once
+        let mut messages: Vec<Vec<u8>> = (0..21).map(|i| vec![i as u8; 100]).collect();
+        messages.extend((0..5).map(|i| vec![i as u8; 64 + i]));
+        messages.push(Vec::new());
+        let inputs: Vec<*const u8> = messages.iter().map(|message| message.as_ptr()).collect();
+        let lengths: Vec<usize> = messages.iter().map(|message| message.len()).collect();
+
+        let mut outs = vec![0u8; messages.len() * 32];
+        assert_eq!(shabal256_hash_many_checked(inputs.as_ptr(), lengths.as_ptr(), messages.len(), outs.as_mut_ptr(), outs.len() - 1), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(shabal256_hash_many_checked(inputs.as_ptr(), lengths.as_ptr(), messages.len(), outs.as_mut_ptr(), outs.len()), libshabal_status::OK);
+
+        for (i, message) in messages.iter().enumerate() {
+            let mut expected = [0u8; 32];
+            shabal256_hash(message.as_ptr(), message.len(), expected.as_mut_ptr());
+            assert_eq!(&outs[i * 32..(i + 1) * 32], &expected[..]);
+            assert_eq!(&expected[..], &shabal::Shabal256::digest(message)[..]);
+        }
+
+        let mut empty = [0u8; 32];
+        assert_eq!(shabal256_hash_checked(null(), 0, empty.as_mut_ptr(), 32), libshabal_status::OK);
+        assert_eq!(shabal256_hash_checked(null(), 1, empty.as_mut_ptr(), 32), libshabal_status::NULL_POINTER);
+        assert_eq!(&empty[..], &outs[outs.len() - 32..]);
+    }
+
+    #[test]
+    fn shabal256_in_place_checked_statuses() {
+        let mut storage = [0u64; 64];
+        let size = libshabal_shabal256_context_size();
+        let context = storage.as_mut_ptr() as *mut c_void;
+        let misaligned = unsafe { (storage.as_mut_ptr() as *mut u8).add(1) } as *mut c_void;
+        let fork = unsafe { (storage.as_mut_ptr() as *mut u8).add(256) } as *mut c_void;
+
+        assert_eq!(shabal256_init_in_place_checked(context, size - 1), libshabal_status::BUFFER_TOO_SMALL);
+        if libshabal_shabal256_context_align() > 1 {
+            assert_eq!(shabal256_init_in_place_checked(misaligned, size), libshabal_status::INVALID_ARGUMENT);
+        }
+        assert_eq!(shabal256_init_in_place_checked(context, size), libshabal_status::OK);
+        assert_eq!(shabal256_update_checked(context, b"gensig".as_ptr(), 6, 0, 6), libshabal_status::OK);
+        assert_eq!(shabal256_clone_checked(null(), fork, size), libshabal_status::NULL_POINTER);
+        assert_eq!(shabal256_clone_checked(context, fork, size), libshabal_status::OK);
+
+        let mut hash = [0u8; 32];
+        assert_eq!(shabal256_digest_checked(fork, hash.as_mut_ptr(), 32, 0), libshabal_status::OK);
+        assert_eq!(&hash[..], &shabal::Shabal256::digest(b"gensig")[..]);
+    }
+
+    /// Deadline of each nonce computed one hash at a time.
+    fn reference_deadlines(scoops: &[u8], gensig: &[u8; 32]) -> Vec<u64> {
+        return scoops.chunks_exact(SCOOP_SIZE).map(|scoop| {
+            let mut shabal = shabal::Shabal256::new();
+            shabal.input(&gensig[..]);
+            shabal.input(scoop);
+            let hash = shabal.result();
+            let mut deadline = [0u8; 8];
+            deadline.copy_from_slice(&hash[..8]);
+            return u64::from_le_bytes(deadline);
+        }).collect();
+    }
+
+    #[test]
+    fn find_best_deadline_matches_reference() {
+        // enough nonces to use the full SIMD batches as well as the single nonce tail
+        let nonce_count = 37;
+        let scoops = test_scoops(nonce_count);
+        for gensig in [[0u8; 32], [0x5a; 32]].iter() {
+            let deadlines = reference_deadlines(&scoops, gensig);
+            let best_offset = (0..deadlines.len()).min_by_key(|&i| deadlines[i]).unwrap();
+            let result = find_best_deadline(&scoops, gensig, &CancellationToken::new(), &mut |_| {});
+            assert_eq!(result, Some((deadlines[best_offset], best_offset as u64)));
+        }
+    }
+
+    #[test]
+    fn shabal256_midstate_deadlines() {
+        let scoops = test_scoops(8);
+        let gensig = [0x5a; 32];
+        let midstate = Shabal256Midstate::new(&gensig).unwrap();
+        let deadlines: Vec<u64> = scoops.chunks_exact(SCOOP_SIZE).map(|scoop| {
+            let mut term = [0u32; 16];
+            for (word, bytes) in term.iter_mut().zip(scoop[32..].chunks_exact(4)) {
+                *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+            term[8] = 0x80;
+            let hash = midstate.hash(&scoop[..32], &term).unwrap();
+            let mut deadline = [0u8; 8];
+            deadline.copy_from_slice(&hash[..8]);
+            return u64::from_le_bytes(deadline);
+        }).collect();
+        assert_eq!(deadlines, reference_deadlines(&scoops, &gensig));
+    }
+
+    /// Deadline throughput, run with `cargo test --release --features simd -- --ignored --nocapture bench_`.
+    #[test]
+    #[ignore]
+    fn bench_find_best_deadline() {
+        let nonce_count = 1 << 20;
+        let scoops = test_scoops(nonce_count);
+        let gensig = [0x5a; 32];
+        let start = std::time::Instant::now();
+        for _ in 0..10 {
+            find_best_deadline(&scoops, &gensig, &CancellationToken::new(), &mut |_| {});
+        }
+        let elapsed = start.elapsed();
+        println!("find_best_deadline: {:.1} Mnonces/s", (10 * nonce_count) as f64 / elapsed.as_secs_f64() / 1e6);
+    }
+
+    #[test]
+    fn nonce_hash_functions() {
+        let data = [0x11u8; 80];
+        let mut hash = [0u8; 32];
+        assert_eq!(nonce_seeded_hash_checked(data.as_ptr(), data.len(), 1, 2, hash.as_mut_ptr(), 32), libshabal_status::OK);
+        assert_eq!(hash, nonce::seeded_hash(&data, 1, 2));
+
+        let term = nonce::termination_block(&data[64..]);
+        assert_eq!(nonce_hash_with_termination_checked(data.as_ptr(), data.len(), term.as_ptr(), hash.as_mut_ptr(), 32), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(nonce_hash_with_termination_checked(data.as_ptr(), 64, term.as_ptr(), hash.as_mut_ptr(), 32), libshabal_status::OK);
+        assert_eq!(&hash[..], &shabal::Shabal256::digest(&data[..])[..]);
+    }
+
+    #[test]
+    fn nonce_hash_chain_matches_create_scoop() {
+        let mut chain = nonce::NonceHashChain::new(10282355196851764065, 3);
+        chain.complete();
+        let mut scoop = [0u8; SCOOP_SIZE];
+        for &poc_version in [1, 2].iter() {
+            assert_eq!(create_scoop_checked(10282355196851764065, 3, 1234, poc_version, scoop.as_mut_ptr(), SCOOP_SIZE, 0), libshabal_status::OK);
+            assert_eq!(chain.scoop(1234, poc_version), Some(scoop));
+        }
+    }
+
+    #[test]
+    fn account_id_functions() {
+        let passphrase = b"correct horse battery staple";
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&sha2::Sha256::digest(&passphrase[..]));
+        let mut public_key = [0u8; 32];
+        curve25519_get_public_key(private_key.as_mut_ptr(), public_key.as_mut_ptr());
+
+        let mut account_id = 0;
+        assert_eq!(account_id_from_public_key_checked(public_key.as_ptr(), 31, &mut account_id), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(account_id_from_public_key_checked(public_key.as_ptr(), 32, &mut account_id), libshabal_status::OK);
+        assert_eq!(account_id, 11623955783251610612);
+        assert_eq!(account_id_from_passphrase(passphrase.as_ptr(), passphrase.len()), account_id);
+        assert_eq!(account_id_from_passphrase_checked(null(), 1, &mut account_id), libshabal_status::NULL_POINTER);
+        assert_eq!(account_id_from_public_key(null()), 0);
+    }
+
+    #[test]
+    fn burst_address_functions() {
+        let mut buffer = [0 as c_char; 32];
+        assert_eq!(burst_address_encode(1739068987193023818, b"BURST\0".as_ptr() as *const c_char, buffer.as_mut_ptr(), 26), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(burst_address_encode(1739068987193023818, b"BURST\0".as_ptr() as *const c_char, buffer.as_mut_ptr(), 27), libshabal_status::OK);
+        let address = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        assert_eq!(address.to_str().unwrap(), "BURST-MRCC-2YLS-8M54-3CMAJ");
+
+        let (mut account_id, mut corrected) = (0, 1);
+        assert_eq!(burst_address_decode(buffer.as_ptr(), &mut account_id, &mut corrected), libshabal_status::OK);
+        assert_eq!((account_id, corrected), (1739068987193023818, 0));
+
+        let typo = b"BURST-MRCC-2YLS-8M54-3CMAK\0".as_ptr() as *const c_char;
+        account_id = 0;
+        assert_eq!(burst_address_decode(typo, &mut account_id, &mut corrected), libshabal_status::INVALID_ADDRESS);
+        assert_eq!((account_id, corrected), (1739068987193023818, 1));
+        assert_eq!(burst_address_decode(b"BURST-MRCC\0".as_ptr() as *const c_char, &mut account_id, &mut corrected), libshabal_status::INVALID_ADDRESS);
+        assert_eq!(corrected, 0);
+    }
+
+    #[test]
+    fn keypair_from_passphrase_matches_get_public_key() {
+        let passphrase = b"correct horse battery staple";
+        let (mut public_key, mut signing_key, mut agreement_key) = ([0u8; 32], [0u8; 32], [0u8; 32]);
+        assert_eq!(keypair_from_passphrase_checked(passphrase.as_ptr(), passphrase.len(), public_key.as_mut_ptr(), 32, signing_key.as_mut_ptr(), 31, agreement_key.as_mut_ptr(), 32), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(keypair_from_passphrase_checked(passphrase.as_ptr(), passphrase.len(), public_key.as_mut_ptr(), 32, signing_key.as_mut_ptr(), 32, agreement_key.as_mut_ptr(), 32), libshabal_status::OK);
+
+        let mut expected_public_key = [0u8; 32];
+        curve25519_get_public_key(agreement_key.as_ptr(), expected_public_key.as_mut_ptr());
+        assert_eq!(public_key, expected_public_key);
+        assert_eq!(account_id_from_public_key(public_key.as_ptr()), 11623955783251610612);
+
+        // the signing key signs as the agreement key does
+        let message_sha256 = [0x13u8; 32];
+        let (mut signature, mut expected_signature) = ([0u8; 64], [0u8; 64]);
+        assert_eq!(curve25519_sign_with_signing_key_checked(signing_key.as_ptr(), 31, message_sha256.as_ptr(), 32, signature.as_mut_ptr(), 64), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(curve25519_sign_with_signing_key_checked(signing_key.as_ptr(), 32, message_sha256.as_ptr(), 32, signature.as_mut_ptr(), 64), libshabal_status::OK);
+        curve25519_sign(agreement_key.as_ptr(), message_sha256.as_ptr(), expected_signature.as_mut_ptr());
+        assert_eq!(signature[..], expected_signature[..]);
+        assert_eq!(curve25519_verify(public_key.as_ptr(), signature.as_ptr(), message_sha256.as_ptr(), 1), 1);
+    }
+
+
+    #[test]
+    fn curve25519_functions_leave_keys_unchanged() {
+        // clamping would clear the low bits of the first byte and the top bit of the last one
+        let private_key = [0xFFu8; 32];
+        let mut public_key = [0u8; 32];
+        curve25519_get_public_key(private_key.as_ptr(), public_key.as_mut_ptr());
+        assert_eq!(curve25519_get_public_key_checked(private_key.as_ptr(), 32, public_key.as_mut_ptr(), 32), libshabal_status::OK);
+        let public_key_before = public_key;
+
+        let mut clamped_key = private_key;
+        clamped_key[0] &= 0xF8;
+        clamped_key[31] &= 0x7F;
+        let mut expected_public_key = [0u8; 32];
+        curve25519_get_public_key(clamped_key.as_ptr(), expected_public_key.as_mut_ptr());
+        assert_eq!(public_key, expected_public_key);
+
+        let mut shared_secret = [0u8; 32];
+        curve25519_get_shared_secret(private_key.as_ptr(), public_key.as_ptr(), shared_secret.as_mut_ptr());
+        assert_eq!(curve25519_get_shared_secret_checked(private_key.as_ptr(), 32, public_key.as_ptr(), 32, shared_secret.as_mut_ptr(), 32), libshabal_status::OK);
+        let mut clamped_shared_secret = [0u8; 32];
+        curve25519_get_shared_secret(clamped_key.as_ptr(), public_key.as_ptr(), clamped_shared_secret.as_mut_ptr());
+        assert_eq!(shared_secret, clamped_shared_secret);
+
+        let message_sha256 = [0x13u8; 32];
+        let mut signature = [0u8; 64];
+        curve25519_sign(private_key.as_ptr(), message_sha256.as_ptr(), signature.as_mut_ptr());
+        assert_eq!(curve25519_sign_checked(private_key.as_ptr(), 32, message_sha256.as_ptr(), 32, signature.as_mut_ptr(), 64), libshabal_status::OK);
+        assert_eq!(curve25519_verify(public_key.as_ptr(), signature.as_ptr(), message_sha256.as_ptr(), 1), 1);
+
+        assert_eq!(private_key, [0xFFu8; 32]);
+        assert_eq!(public_key, public_key_before);
+    }
+
+    #[test]
+    fn curve25519_verify_batch_results() {
+        let count = 40;
+        let mut public_keys = vec![0u8; count * 32];
+        let mut signatures = vec![0u8; count * 64];
+        let message_hashes: Vec<u8> = (0..count * 32).map(|i| (i / 32) as u8).collect();
+        for i in 0..count {
+            let private_key = [i as u8 + 1; 32];
+            curve25519_get_public_key(private_key.as_ptr(), public_keys[i * 32..].as_mut_ptr());
+            curve25519_sign(private_key.as_ptr(), message_hashes[i * 32..].as_ptr(), signatures[i * 64..].as_mut_ptr());
+        }
+
+        let mut results = vec![0u8; count];
+        assert_eq!(curve25519_verify_batch(public_keys.as_ptr(), signatures.as_ptr(), message_hashes.as_ptr(), count, 1, results.as_mut_ptr()), 1);
+        assert!(results.iter().all(|&result| result == 1));
+
+        signatures[7 * 64 + 40] ^= 1;
+        assert_eq!(curve25519_verify_batch_checked(
+            public_keys.as_ptr(), public_keys.len(), signatures.as_ptr(), signatures.len(), message_hashes.as_ptr(), message_hashes.len(),
+            count, 1, results.as_mut_ptr(), results.len(),
+        ), libshabal_status::VERIFICATION_FAILED);
+        assert_eq!(results.iter().position(|&result| result == 0), Some(7));
+        assert_eq!(results.iter().filter(|&&result| result == 0).count(), 1);
+
+        assert_eq!(curve25519_verify_batch_checked(
+            public_keys.as_ptr(), public_keys.len(), signatures.as_ptr(), signatures.len() - 64, message_hashes.as_ptr(), message_hashes.len(),
+            count, 1, results.as_mut_ptr(), results.len(),
+        ), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(curve25519_verify_batch_checked(
+            public_keys.as_ptr(), public_keys.len(), signatures.as_ptr(), signatures.len(), message_hashes.as_ptr(), message_hashes.len(),
+            count, 1, results.as_mut_ptr(), count - 1,
+        ), libshabal_status::BUFFER_TOO_SMALL);
+        // count * 32 fits in a usize but count * 64 does not
+        let huge_count = usize::MAX / 64 + 1;
+        assert_eq!(curve25519_verify_batch_checked(
+            public_keys.as_ptr(), huge_count * 32, signatures.as_ptr(), 0, message_hashes.as_ptr(), huge_count * 32,
+            huge_count, 1, results.as_mut_ptr(), huge_count,
+        ), libshabal_status::INVALID_ARGUMENT);
+        assert_eq!(curve25519_verify_batch(null(), null(), null(), 0, 1, null_mut()), 1);
+    }
+
+    #[test]
+    fn curve25519_message_functions() {
+        let private_key = [0x42u8; 32];
+        let mut public_key = [0u8; 32];
+        curve25519_get_public_key(private_key.as_ptr(), public_key.as_mut_ptr());
+        let message = b"a message which arrives in several pieces";
+        let mut message_sha256 = [0u8; 32];
+        message_sha256.copy_from_slice(&sha2::Sha256::digest(&message[..]));
+
+        let mut expected = [0u8; 64];
+        curve25519_sign(private_key.as_ptr(), message_sha256.as_ptr(), expected.as_mut_ptr());
+        let mut signature = [0u8; 64];
+        curve25519_sign_message(private_key.as_ptr(), message.as_ptr(), message.len(), signature.as_mut_ptr());
+        assert_eq!(signature[..], expected[..]);
+        assert_eq!(curve25519_verify_message(public_key.as_ptr(), signature.as_ptr(), message.as_ptr(), message.len(), 1), 1);
+        assert_eq!(curve25519_verify_message_checked(public_key.as_ptr(), 32, signature.as_ptr(), 64, message.as_ptr(), message.len() - 1, 1), libshabal_status::VERIFICATION_FAILED);
+
+        let hasher = curve25519_message_new();
+        curve25519_message_update(hasher, message.as_ptr(), 0, 10);
+        assert_eq!(curve25519_message_update_checked(hasher, message.as_ptr(), message.len(), 10, message.len()), libshabal_status::BUFFER_TOO_SMALL);
+        assert_eq!(curve25519_message_update_checked(hasher, message.as_ptr(), message.len(), 10, message.len() - 10), libshabal_status::OK);
+        let mut streamed = [0u8; 64];
+        assert_eq!(curve25519_message_sign_checked(hasher, private_key.as_ptr(), 32, streamed.as_mut_ptr(), 64), libshabal_status::OK);
+        assert_eq!(streamed[..], expected[..]);
+
+        // signing reset the hasher
+        curve25519_message_update(hasher, message.as_ptr(), 0, message.len());
+        assert_eq!(curve25519_message_verify_checked(hasher, public_key.as_ptr(), 32, signature.as_ptr(), 64, 1), libshabal_status::OK);
+        assert_eq!(curve25519_message_verify(hasher, public_key.as_ptr(), signature.as_ptr(), 1), 0);
+        assert_eq!(curve25519_message_sign_checked(hasher, private_key.as_ptr(), 31, streamed.as_mut_ptr(), 64), libshabal_status::INVALID_ARGUMENT);
+        curve25519_message_destroy(hasher);
+        assert_eq!(curve25519_sign_message_checked(private_key.as_ptr(), 32, null(), 1, signature.as_mut_ptr(), 64), libshabal_status::NULL_POINTER);
+    }
+
+    #[test]
+    fn burst_encrypt_and_decrypt_data() {
+        let alice = [0x11u8; 32];
+        let bob = [0x22u8; 32];
+        let mut alice_public = [0u8; 32];
+        let mut bob_public = [0u8; 32];
+        curve25519_get_public_key(alice.as_ptr(), alice_public.as_mut_ptr());
+        curve25519_get_public_key(bob.as_ptr(), bob_public.as_mut_ptr());
+        let message = b"an encrypted message";
+
+        let mut nonce = [0u8; 32];
+        let mut written = 0;
+        assert_eq!(burst_encrypt_data(
+            message.as_ptr(), message.len(), alice.as_ptr(), 32, bob_public.as_ptr(), 32, nonce.as_mut_ptr(), 32, null_mut(), 0, &mut written,
+        ), libshabal_status::BUFFER_TOO_SMALL);
+        let mut data = vec![0u8; written];
+        assert_eq!(burst_encrypt_data(
+            message.as_ptr(), message.len(), alice.as_ptr(), 32, bob_public.as_ptr(), 32, nonce.as_mut_ptr(), 32, data.as_mut_ptr(), data.len(), &mut written,
+        ), libshabal_status::OK);
+        assert_eq!(written, data.len());
+
+        let mut plaintext = [0u8; 64];
+        assert_eq!(burst_decrypt_data(
+            data.as_ptr(), data.len(), nonce.as_ptr(), 32, bob.as_ptr(), 32, alice_public.as_ptr(), 32, plaintext.as_mut_ptr(), plaintext.len(), &mut written,
+        ), libshabal_status::OK);
+        assert_eq!(plaintext[..written], message[..]);
+
+        nonce[0] ^= 1;
+        assert_eq!(burst_decrypt_data(
+            data.as_ptr(), data.len(), nonce.as_ptr(), 32, bob.as_ptr(), 32, alice_public.as_ptr(), 32, plaintext.as_mut_ptr(), plaintext.len(), &mut written,
+        ), libshabal_status::DECRYPTION_FAILED);
+        assert_eq!(burst_decrypt_data(
+            data.as_ptr(), data.len(), nonce.as_ptr(), 31, bob.as_ptr(), 32, alice_public.as_ptr(), 32, plaintext.as_mut_ptr(), plaintext.len(), &mut written,
+        ), libshabal_status::INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn burst_reward_recipient_assignment_matches_transaction() {
+        let passphrase = b"correct horse battery staple";
+        let mut bytes = [0u8; transaction::REWARD_RECIPIENT_ASSIGNMENT_SIZE];
+        let mut id = 0;
+        assert_eq!(burst_reward_recipient_assignment(passphrase.as_ptr(), passphrase.len(), 42, 735_000, 1000, 500_000, 77, bytes.as_mut_ptr(), bytes.len(), &mut id), libshabal_status::OK);
+        let expected = transaction::assign_reward_recipient(passphrase, 42, 735_000, 1000, 500_000, 77);
+        assert_eq!(bytes[..], expected.to_bytes()[..]);
+        assert_eq!(Some(id), expected.id());
+
+        assert_eq!(burst_reward_recipient_assignment(passphrase.as_ptr(), passphrase.len(), 42, 735_000, 0, 500_000, 77, bytes.as_mut_ptr(), bytes.len(), &mut id), libshabal_status::OK);
+        assert!(transaction::Transaction::from_bytes(&bytes).unwrap().timestamp > 300_000_000);
+        assert_eq!(burst_reward_recipient_assignment(passphrase.as_ptr(), passphrase.len(), 42, 735_000, 0, 500_000, 77, bytes.as_mut_ptr(), bytes.len() - 1, &mut id), libshabal_status::BUFFER_TOO_SMALL);
+    }
+
+    #[test]
+    fn curve25519_canonical_functions() {
+        let private_key = [0x42u8; 32];
+        let mut public_key = [0u8; 32];
+        let mut signature = [0u8; 64];
+        curve25519_get_public_key(private_key.as_ptr(), public_key.as_mut_ptr());
+        curve25519_sign(private_key.as_ptr(), [0x13u8; 32].as_ptr(), signature.as_mut_ptr());
+        assert_eq!(curve25519_is_canonical_signature(signature.as_ptr()), 1);
+        assert_eq!(curve25519_is_canonical_public_key_checked(public_key.as_ptr(), 32), libshabal_status::OK);
+
+        // an all ones signature and public key are far above the group order and the prime
+        let mut malleated = [0xFFu8; 64];
+        assert_eq!(curve25519_is_canonical_signature_checked(malleated.as_ptr(), 64), libshabal_status::NON_CANONICAL);
+        assert_eq!(curve25519_is_canonical_public_key(malleated.as_ptr()), 0);
+        let mut canonical_public_key = [0u8; 32];
+        assert_eq!(curve25519_canonicalize_public_key_checked(malleated.as_ptr(), 32, canonical_public_key.as_mut_ptr(), 32), libshabal_status::OK);
+        assert_eq!(curve25519_is_canonical_public_key(canonical_public_key.as_ptr()), 1);
+        curve25519_canonicalize_signature(malleated.as_ptr(), malleated.as_mut_ptr());
+        assert_eq!(curve25519_is_canonical_signature(malleated.as_ptr()), 1);
+        assert_eq!(malleated[32..], [0xFFu8; 32]);
+        assert_eq!(curve25519_canonicalize_signature_checked(malleated.as_ptr(), 63, malleated.as_mut_ptr(), 64), libshabal_status::INVALID_ARGUMENT);
     }
 }
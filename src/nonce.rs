@@ -0,0 +1,222 @@
+//! Hashing of nonce-format messages, for reproducing individual steps of nonce generation.
+//!
+//! A nonce is `NONCE_SIZE` bytes made of 8192 hashes, generated from the last one backwards.
+//! Each hash is Shabal256 of the hashes generated so far (the ones after it), capped to the last
+//! `HASH_CAP` bytes, and while that is shorter than `HASH_CAP` followed by the 16 byte seed:
+//!
+//! * hash 8191 (the last 32 bytes) is `seeded_hash(&[], account_id, nonce)`
+//! * hash `i` is `seeded_hash(&nonce_data[(i + 1) * 32..], account_id, nonce)` while fewer than 128 hashes follow it
+//! * otherwise hash `i` is `hash(&nonce_data[(i + 1) * 32..(i + 1) * 32 + HASH_CAP])`
+//!
+//! Finally every byte of the nonce is XORed with the matching byte of
+//! `seeded_hash(&nonce_data, account_id, nonce)`, and PoC2 swaps the second hash of every scoop with
+//! the one of the mirrored scoop (scoop 0 with 4095, 1 with 4094 and so on).
+
+use crate::pocc::shabal256_fast::{shabal256_fast, Shabal256Midstate};
+
+pub use crate::pocc::plot::{NONCE_SIZE, NUM_SCOOPS, SCOOP_SIZE};
+
+pub const HASH_SIZE: usize = 32;
+/// Maximum number of bytes of preceding hashes that go into a hash.
+pub const HASH_CAP: usize = 4096;
+pub const SEED_SIZE: usize = 16;
+/// Number of hashes in a nonce.
+pub const NUM_HASHES: usize = NONCE_SIZE / HASH_SIZE;
+
+const BLOCK_SIZE: usize = 64;
+
+/// The seed appended to the early hashes of a nonce: the account id followed by the nonce number, both big endian.
+pub fn seed(account_id: u64, nonce: u64) -> [u8; SEED_SIZE] {
+    let mut seed = [0u8; SEED_SIZE];
+    seed[..8].copy_from_slice(&account_id.to_be_bytes());
+    seed[8..].copy_from_slice(&nonce.to_be_bytes());
+    return seed;
+}
+
+/// Shabal256 of `data` followed by the seed of `account_id` and `nonce`.
+pub fn seeded_hash(data: &[u8], account_id: u64, nonce: u64) -> [u8; HASH_SIZE] {
+    return hash_with_tail(data, &seed(account_id, nonce));
+}
+
+/// Shabal256 of `message`.
+pub fn hash(message: &[u8]) -> [u8; HASH_SIZE] {
+    return hash_with_tail(message, &[]);
+}
+
+/// Shabal256 of whole 64 byte blocks of `data` closed by the termination block `term`.
+///
+/// This is the primitive nonce generation is built on. `term` holds the message bytes after the
+/// last whole block followed by the `0x80` padding byte and zeros, as little endian words -
+/// `termination_block` builds it from those bytes. Any bytes of `data` after its last whole block are ignored.
+pub fn hash_with_termination(data: &[u8], term: &[u32; 16]) -> [u8; HASH_SIZE] {
+    return shabal256_fast(data, term);
+}
+
+/// Builds the termination block for the final `tail` bytes of a message, which must be shorter than 64 bytes.
+pub fn termination_block(tail: &[u8]) -> [u32; 16] {
+    assert!(tail.len() < BLOCK_SIZE, "the tail of a message must be shorter than a block");
+    let mut block = [0u8; BLOCK_SIZE];
+    block[..tail.len()].copy_from_slice(tail);
+    block[tail.len()] = 0x80;
+    let mut term = [0u32; 16];
+    for (word, bytes) in term.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    return term;
+}
+
+/// The hash chain of a nonce, generated incrementally.
+///
+/// Hashes are generated from the last one (`NUM_HASHES - 1`) backwards and hash `i` depends on the
+/// up to 128 hashes after it. The final hash that every byte of the nonce is XORed with covers all of
+/// them though, so a scoop can only be computed once the whole chain is complete. What a verifier saves
+/// over generating the nonce is applying the final XOR and the PoC2 shuffle to the one scoop it needs.
+#[derive(Debug, Clone)]
+pub struct NonceHashChain {
+    account_id: u64,
+    nonce: u64,
+    chain: Vec<u8>,
+    next: usize,
+    final_hash: Option<[u8; HASH_SIZE]>,
+}
+
+impl NonceHashChain {
+    pub fn new(account_id: u64, nonce: u64) -> Self {
+        return NonceHashChain {
+            account_id,
+            nonce,
+            chain: vec![0u8; NONCE_SIZE],
+            next: NUM_HASHES,
+            final_hash: None,
+        };
+    }
+
+    /// Generates the hashes from the last one down to and including hash `index`.
+    pub fn generate_to(&mut self, index: usize) {
+        assert!(index < NUM_HASHES, "a nonce only has {} hashes", NUM_HASHES);
+        while self.next > index {
+            let start = self.next * HASH_SIZE;
+            let hash = if NONCE_SIZE - start < HASH_CAP {
+                seeded_hash(&self.chain[start..], self.account_id, self.nonce)
+            } else {
+                hash(&self.chain[start..start + HASH_CAP])
+            };
+            self.next -= 1;
+            self.chain[start - HASH_SIZE..start].copy_from_slice(&hash);
+        }
+        if self.next == 0 && self.final_hash.is_none() {
+            self.final_hash = Some(seeded_hash(&self.chain, self.account_id, self.nonce));
+        }
+    }
+
+    /// Generates the rest of the chain and the final hash.
+    pub fn complete(&mut self) {
+        self.generate_to(0);
+    }
+
+    /// Hash `index` of the chain, before the final XOR, if it has been generated yet.
+    pub fn hash(&self, index: usize) -> Option<&[u8]> {
+        if index < self.next || index >= NUM_HASHES { return None; }
+        return Some(&self.chain[index * HASH_SIZE..(index + 1) * HASH_SIZE]);
+    }
+
+    /// The hashes generated so far, in their position at the end of the nonce.
+    pub fn generated(&self) -> &[u8] {
+        return &self.chain[self.next * HASH_SIZE..];
+    }
+
+    /// The hash every byte of the nonce is XORed with, available once the chain is complete.
+    pub fn final_hash(&self) -> Option<&[u8; HASH_SIZE]> {
+        return self.final_hash.as_ref();
+    }
+
+    /// Scoop `scoop` of the nonce for `poc_version` 1 or 2, available once the chain is complete.
+    pub fn scoop(&self, scoop: usize, poc_version: u8) -> Option<[u8; SCOOP_SIZE]> {
+        assert!(scoop < NUM_SCOOPS, "a nonce only has {} scoops", NUM_SCOOPS);
+        let final_hash = self.final_hash.as_ref()?;
+        // PoC2 takes the second hash of each scoop from the mirrored scoop
+        let second_hash_scoop = if poc_version == 2 { NUM_SCOOPS - 1 - scoop } else { scoop };
+        let mut result = [0u8; SCOOP_SIZE];
+        result[..HASH_SIZE].copy_from_slice(&self.chain[scoop * SCOOP_SIZE..scoop * SCOOP_SIZE + HASH_SIZE]);
+        result[HASH_SIZE..].copy_from_slice(&self.chain[second_hash_scoop * SCOOP_SIZE + HASH_SIZE..(second_hash_scoop + 1) * SCOOP_SIZE]);
+        for (byte, final_byte) in result.iter_mut().zip(final_hash.iter().cycle()) {
+            *byte ^= final_byte;
+        }
+        return Some(result);
+    }
+}
+
+/// Shabal256 of `data` followed by `suffix`, without copying `data`.
+fn hash_with_tail(data: &[u8], suffix: &[u8]) -> [u8; HASH_SIZE] {
+    let whole_blocks = data.len() - data.len() % BLOCK_SIZE;
+    let mut tail = data[whole_blocks..].to_vec();
+    tail.extend_from_slice(suffix);
+    if tail.len() < BLOCK_SIZE {
+        return shabal256_fast(&data[..whole_blocks], &termination_block(&tail));
+    }
+    let midstate = Shabal256Midstate::new(&data[..whole_blocks]).expect("whole blocks are whole words");
+    let (block, rest) = tail.split_at(BLOCK_SIZE);
+    return midstate.hash(block, &termination_block(rest)).expect("a prefix of whole blocks leaves no partial block");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pocc::plot::noncegen_single_rust;
+    use crate::shabal::{Digest, Shabal256};
+
+    #[test]
+    fn hashes_match_shabal256() {
+        let data: Vec<u8> = (0..200u32).map(|i| (i * 13) as u8).collect();
+        let seed = seed(0x0102030405060708, 42);
+        for &len in [0, 31, 47, 48, 63, 64, 100, 200].iter() {
+            assert_eq!(hash(&data[..len])[..], Shabal256::digest(&data[..len])[..]);
+            let mut seeded = data[..len].to_vec();
+            seeded.extend_from_slice(&seed);
+            assert_eq!(seeded_hash(&data[..len], 0x0102030405060708, 42)[..], Shabal256::digest(&seeded)[..]);
+        }
+    }
+
+    #[test]
+    fn hash_chain_scoops_match_nonce() {
+        let (account_id, nonce) = (10282355196851764065, 7);
+        let mut chain = NonceHashChain::new(account_id, nonce);
+        chain.generate_to(NUM_HASHES - 128);
+        assert_eq!(chain.generated().len(), 128 * HASH_SIZE);
+        assert_eq!(chain.hash(NUM_HASHES - 1).unwrap(), &seeded_hash(&[], account_id, nonce)[..]);
+        assert!(chain.hash(NUM_HASHES - 129).is_none());
+        assert!(chain.scoop(0, 1).is_none());
+        chain.complete();
+
+        for &poc_version in [1, 2].iter() {
+            let mut expected = vec![0u8; NONCE_SIZE];
+            noncegen_single_rust(&mut expected, account_id, nonce, poc_version);
+            for &scoop in [0, 1, 2047, 2048, 4095].iter() {
+                assert_eq!(&chain.scoop(scoop, poc_version).unwrap()[..], &expected[scoop * SCOOP_SIZE..(scoop + 1) * SCOOP_SIZE]);
+            }
+        }
+    }
+
+    #[test]
+    fn steps_reproduce_nonce() {
+        let (account_id, nonce) = (10282355196851764065, 7);
+        let mut expected = vec![0u8; NONCE_SIZE];
+        noncegen_single_rust(&mut expected, account_id, nonce, 1);
+
+        let mut nonce_data = vec![0u8; NONCE_SIZE];
+        for i in (0..NONCE_SIZE / HASH_SIZE).rev() {
+            let start = (i + 1) * HASH_SIZE;
+            let step = if NONCE_SIZE - start < HASH_CAP {
+                seeded_hash(&nonce_data[start..], account_id, nonce)
+            } else {
+                hash(&nonce_data[start..start + HASH_CAP])
+            };
+            nonce_data[i * HASH_SIZE..start].copy_from_slice(&step);
+        }
+        let final_hash = seeded_hash(&nonce_data, account_id, nonce);
+        for (i, byte) in nonce_data.iter_mut().enumerate() {
+            *byte ^= final_hash[i % HASH_SIZE];
+        }
+        assert!(nonce_data == expected);
+    }
+}
@@ -63,3 +63,10 @@ void write_term(char term[32]);
         *best_deadline = (d);   \
         *best_offset = (o);     \
     }
+
+// cancellation flag shared with the caller, may be NULL; polled between batches
+#ifdef _MSC_VER
+#define IS_CANCELLED(flag) ((flag) != NULL && *(volatile const uint8_t *)(flag) != 0)
+#else
+#define IS_CANCELLED(flag) ((flag) != NULL && __atomic_load_n((flag), __ATOMIC_RELAXED) != 0)
+#endif
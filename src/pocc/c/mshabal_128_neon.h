@@ -155,6 +155,13 @@ void mshabal_close_neon(mshabal128_context *sc, unsigned ub0, unsigned ub1, unsi
                            unsigned ub3, unsigned n, void *dst0, void *dst1, void *dst2,
                            void *dst3);
 
+/*
+ * Absorb the message words shared by every lane (the generation signature) into a context
+ * so they are not processed again by each mshabal_deadline_fast_neon call using it.
+ * Must be called once on a fresh context before mshabal_deadline_fast_neon.
+ */
+void mshabal_deadline_prefix_neon(mshabal128_context_fast *sc, void *message);
+
 /*
  * optimised Shabal routine for PoC mining
  */
@@ -660,6 +660,24 @@ void mshabal_hash_fast_sse2(mshabal128_context_fast *sc, void *message, void *te
 }
 
 // Shabal routine optimized for mining
+void mshabal_deadline_prefix_sse2(mshabal128_context_fast *sc, void *message) {
+    size_t j;
+    __m128i A0, A1, B;
+
+    // the first 8 message words (the generation signature) are the same for every batch of scoops,
+    // so their part of the first block is added and rotated into the context once up front
+    for (j = 0; j < 8; j++) {
+        B = _mm_add_epi32(_mm_loadu_si128((__m128i *)sc->state + j + 12), _mm_load_si128((__m128i *)message + j));
+        B = _mm_or_si128(_mm_slli_epi32(B, 17), _mm_srli_epi32(B, 15));
+        _mm_storeu_si128((__m128i *)sc->state + j + 12, B);
+    }
+
+    A0 = _mm_xor_si128(_mm_loadu_si128((__m128i *)sc->state + 0), _mm_set1_epi32(sc->Wlow));
+    A1 = _mm_xor_si128(_mm_loadu_si128((__m128i *)sc->state + 1), _mm_set1_epi32(sc->Whigh));
+    _mm_storeu_si128((__m128i *)sc->state + 0, A0);
+    _mm_storeu_si128((__m128i *)sc->state + 1, A1);
+}
+
 void mshabal_deadline_fast_sse2(mshabal128_context_fast *sc, void *message, void *termination, void *dst0,
                                          void *dst1, void *dst2, void *dst3) {
     union input {
@@ -680,12 +698,10 @@ void mshabal_deadline_fast_sse2(mshabal128_context_fast *sc, void *message, void
     // round 1
 #define M(i) _mm_load_si128((__m128i *)message + i)
 
-    for (j = 0; j < 16; j++) B[j] = _mm_add_epi32(B[j], M(j));
-
-    A[0] = _mm_xor_si128(A[0], _mm_set1_epi32(sc->Wlow));
-    A[1] = _mm_xor_si128(A[1], _mm_set1_epi32(sc->Whigh));
+    // the prefix words and the first W counter were absorbed by mshabal_deadline_prefix_sse2
+    for (j = 8; j < 16; j++) B[j] = _mm_add_epi32(B[j], M(j));
 
-    for (j = 0; j < 16; j++)
+    for (j = 8; j < 16; j++)
         B[j] = _mm_or_si128(_mm_slli_epi32(B[j], 17), _mm_srli_epi32(B[j], 15));
 
 #define PP(xa0, xa1, xb0, xb1, xb2, xb3, xc, xm)                                                   \
@@ -161,6 +161,13 @@ void mshabal_close_sse2(mshabal128_context *sc, unsigned ub0, unsigned ub1, unsi
 void mshabal_hash_fast_sse2(mshabal128_context_fast *sc, void *message, void *termination,
                                void *dst, unsigned num);
 
+/*
+ * Absorb the message words shared by every lane (the generation signature) into a context
+ * so they are not processed again by each mshabal_deadline_fast_sse2 call using it.
+ * Must be called once on a fresh context before mshabal_deadline_fast_sse2.
+ */
+void mshabal_deadline_prefix_sse2(mshabal128_context_fast *sc, void *message);
+
 /*
  * optimised Shabal routine for PoC mining
  */
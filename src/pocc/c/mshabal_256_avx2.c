@@ -777,6 +777,24 @@ void mshabal_hash_fast_avx2(mshabal256_context_fast *sc, void *message, void *te
 }
 
 // Shabal routine optimized for mining
+void mshabal_deadline_prefix_avx2(mshabal256_context_fast *sc, void *message) {
+    size_t j;
+    __m256i A0, A1, B;
+
+    // the first 8 message words (the generation signature) are the same for every batch of scoops,
+    // so their part of the first block is added and rotated into the context once up front
+    for (j = 0; j < 8; j++) {
+        B = _mm256_add_epi32(_mm256_loadu_si256((__m256i *)sc->state + j + 12), _mm256_load_si256((__m256i *)message + j));
+        B = _mm256_or_si256(_mm256_slli_epi32(B, 17), _mm256_srli_epi32(B, 15));
+        _mm256_storeu_si256((__m256i *)sc->state + j + 12, B);
+    }
+
+    A0 = _mm256_xor_si256(_mm256_loadu_si256((__m256i *)sc->state + 0), _mm256_set1_epi32(sc->Wlow));
+    A1 = _mm256_xor_si256(_mm256_loadu_si256((__m256i *)sc->state + 1), _mm256_set1_epi32(sc->Whigh));
+    _mm256_storeu_si256((__m256i *)sc->state + 0, A0);
+    _mm256_storeu_si256((__m256i *)sc->state + 1, A1);
+}
+
 void mshabal_deadline_fast_avx2(mshabal256_context_fast *sc, void *message, void *termination, void *dst0, void *dst1, void *dst2, 
                                           void *dst3, void *dst4, void *dst5, void *dst6, void *dst7) {
     union input {
@@ -797,12 +815,10 @@ void mshabal_deadline_fast_avx2(mshabal256_context_fast *sc, void *message, void
     // round 1
 #define M(i) _mm256_loadu_si256((__m256i *)message + i)
 
-    for (j = 0; j < 16; j++) B[j] = _mm256_add_epi32(B[j], M(j));
-
-    A[0] = _mm256_xor_si256(A[0], _mm256_set1_epi32(sc->Wlow));
-    A[1] = _mm256_xor_si256(A[1], _mm256_set1_epi32(sc->Whigh));
+    // the prefix words and the first W counter were absorbed by mshabal_deadline_prefix_avx2
+    for (j = 8; j < 16; j++) B[j] = _mm256_add_epi32(B[j], M(j));
 
-    for (j = 0; j < 16; j++)
+    for (j = 8; j < 16; j++)
         B[j] = _mm256_or_si256(_mm256_slli_epi32(B[j], 17), _mm256_srli_epi32(B[j], 15));
 
 #define PP256(xa0, xa1, xb0, xb1, xb2, xb3, xc, xm)                                   \
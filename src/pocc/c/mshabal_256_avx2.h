@@ -166,6 +166,13 @@ void mshabal_close_avx2(mshabal256_context *sc, unsigned ub0, unsigned ub1, unsi
 void mshabal_hash_fast_avx2(mshabal256_context_fast *sc, void *message, void *termination,
                                void *dst, unsigned len);
 
+/*
+ * Absorb the message words shared by every lane (the generation signature) into a context
+ * so they are not processed again by each mshabal_deadline_fast_avx2 call using it.
+ * Must be called once on a fresh context before mshabal_deadline_fast_avx2.
+ */
+void mshabal_deadline_prefix_avx2(mshabal256_context_fast *sc, void *message);
+
 /*
  * optimised Shabal routine for PoC mining
  */
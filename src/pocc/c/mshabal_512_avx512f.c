@@ -720,7 +720,7 @@ void mshabal_hash_fast_avx512f(mshabal512_context_fast *sc, void *message, void
     one = _mm512_set1_epi32(C32(0xFFFFFFFF));
 
     // round 1
-#define M(i) _mm512_load_si512((__m512i *)message + i)
+#define M(i) _mm512_loadu_si512((__m512i *)message + i)
 
     while (num-- > 0) {
         for (j = 0; j < 16; j++) B[j] = _mm512_add_epi32(B[j], M(j));
@@ -995,9 +995,29 @@ void mshabal_hash_fast_avx512f(mshabal512_context_fast *sc, void *message, void
     // reset Wlow & Whigh
     sc->Wlow = 1;
     sc->Whigh = 0;
+
+#undef M
 }
 
 // Shabal routine optimized for mining
+void mshabal_deadline_prefix_avx512f(mshabal512_context_fast *sc, void *message) {
+    size_t j;
+    __m512i A0, A1, B;
+
+    // the first 8 message words (the generation signature) are the same for every batch of scoops,
+    // so their part of the first block is added and rotated into the context once up front
+    for (j = 0; j < 8; j++) {
+        B = _mm512_add_epi32(_mm512_loadu_si512((__m512i *)sc->state + j + 12), _mm512_load_si512((__m512i *)message + j));
+        B = _mm512_or_si512(_mm512_slli_epi32(B, 17), _mm512_srli_epi32(B, 15));
+        _mm512_storeu_si512((__m512i *)sc->state + j + 12, B);
+    }
+
+    A0 = _mm512_xor_si512(_mm512_loadu_si512((__m512i *)sc->state + 0), _mm512_set1_epi32(sc->Wlow));
+    A1 = _mm512_xor_si512(_mm512_loadu_si512((__m512i *)sc->state + 1), _mm512_set1_epi32(sc->Whigh));
+    _mm512_storeu_si512((__m512i *)sc->state + 0, A0);
+    _mm512_storeu_si512((__m512i *)sc->state + 1, A1);
+}
+
 void mshabal_deadline_fast_avx512f(mshabal512_context_fast *sc, void *message, void *termination, void *dst0,
                                     void *dst1, void *dst2, void *dst3, void *dst4, void *dst5,
                                     void *dst6, void *dst7, void *dst8, void *dst9, void *dst10,
@@ -1021,12 +1041,10 @@ void mshabal_deadline_fast_avx512f(mshabal512_context_fast *sc, void *message, v
     // round 1
 #define M(i) _mm512_load_si512((__m512i *)message + i)
 
-    for (j = 0; j < 16; j++) B[j] = _mm512_add_epi32(B[j], M(j));
-
-    A[0] = _mm512_xor_si512(A[0], _mm512_set1_epi32(sc->Wlow));
-    A[1] = _mm512_xor_si512(A[1], _mm512_set1_epi32(sc->Whigh));
+    // the prefix words and the first W counter were absorbed by mshabal_deadline_prefix_avx512f
+    for (j = 8; j < 16; j++) B[j] = _mm512_add_epi32(B[j], M(j));
 
-    for (j = 0; j < 16; j++)
+    for (j = 8; j < 16; j++)
         B[j] = _mm512_or_si512(_mm512_slli_epi32(B[j], 17), _mm512_srli_epi32(B[j], 15));
 
 #define PP512(xa0, xa1, xb0, xb1, xb2, xb3, xc, xm)                                   \
@@ -5,9 +5,9 @@
 #include "mshabal_128_sse2.h"
 #include "sph_shabal.h"
 
-sph_shabal_context global_32;
-mshabal128_context global_128;
-mshabal128_context_fast global_128_fast;
+static sph_shabal_context global_32;
+static mshabal128_context global_128;
+static mshabal128_context_fast global_128_fast;
 
 void init_noncegen_sse2() {
     sph_shabal256_init(&global_32);
@@ -23,10 +23,12 @@ void init_noncegen_sse2() {
 // numeric_id:		numeric account id
 // loc_startnonce	nonce to start generation at
 // local_nonces: 	number of nonces to generate
+// cancelled:		flag polled between batches, may be NULL
 void noncegen_sse2(char *cache,
                    const uint64_t numeric_id, const uint64_t local_startnonce,
                    const uint64_t local_nonces,
-                   char poc_version) {
+                   char poc_version,
+                   const uint8_t *cancelled) {
     sph_shabal_context local_32;
     uint64_t nonce;
     size_t len;
@@ -83,6 +85,7 @@ void noncegen_sse2(char *cache,
     }
 
        for (uint64_t n = 0; n < local_nonces;) {
+        if (IS_CANCELLED(cancelled)) break;
         // iterate nonces (4 per cycle - sse)
         // min 4 nonces left for sse processing, otherwise SISD
         if (n + 4 <= local_nonces) {
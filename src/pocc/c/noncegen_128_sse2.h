@@ -7,4 +7,5 @@ void init_noncegen_sse2();
 void noncegen_sse2(char *cache,
                    const uint64_t numeric_id, const uint64_t local_startnonce,
                    const uint64_t local_nonces,
-                   char poc_version);
+                   char poc_version,
+                   const uint8_t *cancelled);
@@ -5,9 +5,9 @@
 #include "mshabal_256_avx2.h"
 #include "sph_shabal.h"
 
-sph_shabal_context global_32;
-mshabal256_context global_256;
-mshabal256_context_fast global_256_fast;
+static sph_shabal_context global_32;
+static mshabal256_context global_256;
+static mshabal256_context_fast global_256_fast;
 
 void init_noncegen_avx2() {
     sph_shabal256_init(&global_32);
@@ -23,10 +23,12 @@ void init_noncegen_avx2() {
 // numeric_id:		numeric account id
 // loc_startnonce	nonce to start generation at
 // local_nonces: 	number of nonces to generate
+// cancelled:		flag polled between batches, may be NULL
 void noncegen_avx2(char *cache,
                    const uint64_t numeric_id, const uint64_t local_startnonce,
                    const uint64_t local_nonces,
-                   char poc_version) {
+                   char poc_version,
+                   const uint8_t *cancelled) {
     sph_shabal_context local_32;
     uint64_t nonce;
     size_t len;
@@ -103,6 +105,7 @@ void noncegen_avx2(char *cache,
     }
 
     for (uint64_t n = 0; n < local_nonces;) {
+        if (IS_CANCELLED(cancelled)) break;
         // iterate nonces (8 per cycle - avx2)
         // min 8 nonces left for avx 2 processing, otherwise SISD
         if (n + 8 <= local_nonces) {
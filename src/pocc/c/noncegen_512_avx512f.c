@@ -5,9 +5,9 @@
 #include "mshabal_512_avx512f.h"
 #include "sph_shabal.h"
 
-sph_shabal_context global_32;
-mshabal512_context global_512;
-mshabal512_context_fast global_512_fast;
+static sph_shabal_context global_32;
+static mshabal512_context global_512;
+static mshabal512_context_fast global_512_fast;
 
 void init_noncegen_avx512f() {
     sph_shabal256_init(&global_32);
@@ -23,10 +23,12 @@ void init_noncegen_avx512f() {
 // numeric_id:		numeric account id
 // loc_startnonce	nonce to start generation at
 // local_nonces: 	number of nonces to generate
+// cancelled:		flag polled between batches, may be NULL
 void noncegen_avx512(char *cache,
                    const uint64_t numeric_id, const uint64_t local_startnonce,
                    const uint64_t local_nonces,
-                   char poc_version) {
+                   char poc_version,
+                   const uint8_t *cancelled) {
     sph_shabal_context local_32;
     uint64_t nonce;
     size_t len;
@@ -144,6 +146,7 @@ void noncegen_avx512(char *cache,
     }
 
     for (uint64_t n = 0; n < local_nonces;) {
+        if (IS_CANCELLED(cancelled)) break;
         // iterate nonces (16 per cycle - avx512)
         // min 16 nonces left for avx512 processing, otherwise SISD
         if (n + 16 <= local_nonces) {
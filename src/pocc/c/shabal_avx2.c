@@ -5,8 +5,8 @@
 #include "mshabal_256_avx2.h"
 #include "sph_shabal.h"
 
-mshabal256_context global_256;
-mshabal256_context_fast global_256_fast;
+static mshabal256_context global_256;
+static mshabal256_context_fast global_256_fast;
 
 void init_shabal_avx2() {
     mshabal_init_avx2(&global_256, 256);
@@ -17,7 +17,8 @@ void init_shabal_avx2() {
 }
 
 void find_best_deadline_avx2(char *scoops, uint64_t nonce_count, char *gensig,
-                             uint64_t *best_deadline, uint64_t *best_offset) {
+                             uint64_t *best_deadline, uint64_t *best_offset,
+                             const uint8_t *cancelled) {
     uint64_t d0 = 0, d1 = 0, d2 = 0, d3 = 0, d4 = 0, d5 = 0, d6 = 0, d7 = 0;
     char term[32];
     write_term(term);
@@ -52,7 +53,11 @@ void find_best_deadline_avx2(char *scoops, uint64_t nonce_count, char *gensig,
         u2.words[i + 7 + 64] = *(mshabal_u32 *)(term + o);
     }
 
+    // the generation signature is the same for every batch, so absorb it once
+    mshabal_deadline_prefix_avx2(&x, &u1);
+
     for (uint64_t i = 0; i < nonce_count;) {
+        if (IS_CANCELLED(cancelled)) return;
         if (i + 8 <= nonce_count) {
             // load and align data for SIMD
             for (uint64_t j = 0; j < 16 * MSHABAL256_VECTOR_SIZE / 2; j += MSHABAL256_VECTOR_SIZE) {
@@ -93,3 +98,18 @@ void find_best_deadline_avx2(char *scoops, uint64_t nonce_count, char *gensig,
         }
     }
 }
+
+void hash_lanes_avx2(const char **inputs, size_t len, size_t lane_count, char **outputs) {
+    // unused lanes are deactivated by passing NULL
+    const char *in[MSHABAL256_VECTOR_SIZE] = {NULL};
+    char *out[MSHABAL256_VECTOR_SIZE] = {NULL};
+    for (size_t i = 0; i < lane_count && i < MSHABAL256_VECTOR_SIZE; i++) {
+        in[i] = inputs[i];
+        out[i] = outputs[i];
+    }
+
+    mshabal256_context x;
+    mshabal_init_avx2(&x, 256);
+    mshabal_avx2(&x, in[0], in[1], in[2], in[3], in[4], in[5], in[6], in[7], len);
+    mshabal_close_avx2(&x, 0, 0, 0, 0, 0, 0, 0, 0, 0, out[0], out[1], out[2], out[3], out[4], out[5], out[6], out[7]);
+}
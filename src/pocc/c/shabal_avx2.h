@@ -6,4 +6,7 @@
 void init_shabal_avx2();
 
 void find_best_deadline_avx2(char *scoops, uint64_t nonce_count, char *gensig,
-                             uint64_t *best_deadline, uint64_t *best_offset);
+                             uint64_t *best_deadline, uint64_t *best_offset,
+                             const uint8_t *cancelled);
+
+void hash_lanes_avx2(const char **inputs, size_t len, size_t lane_count, char **outputs);
@@ -5,8 +5,8 @@
 #include "mshabal_512_avx512f.h"
 #include "sph_shabal.h"
 
-mshabal512_context global_512;
-mshabal512_context_fast global_512_fast;
+static mshabal512_context global_512;
+static mshabal512_context_fast global_512_fast;
 
 void init_shabal_avx512f() {
     mshabal_init_avx512f(&global_512, 256);
@@ -17,7 +17,8 @@ void init_shabal_avx512f() {
 }
 
 void find_best_deadline_avx512f(char *scoops, uint64_t nonce_count, char *gensig,
-                                uint64_t *best_deadline, uint64_t *best_offset) {
+                                uint64_t *best_deadline, uint64_t *best_offset,
+                                const uint8_t *cancelled) {
     uint64_t d0 = 0, d1 = 0, d2 = 0, d3 = 0, d4 = 0, d5 = 0, d6 = 0, d7 = 0, d8 = 0, d9 = 0,
              d10 = 0, d11 = 0, d12 = 0, d13 = 0, d14 = 0, d15 = 0;
     char term[32];
@@ -69,7 +70,11 @@ void find_best_deadline_avx512f(char *scoops, uint64_t nonce_count, char *gensig
         u2.words[i + 15 + 128] = *(mshabal_u32 *)(term + o);
     }
 
+    // the generation signature is the same for every batch, so absorb it once
+    mshabal_deadline_prefix_avx512f(&x, &u1);
+
     for (uint64_t i = 0; i < nonce_count;) {
+        if (IS_CANCELLED(cancelled)) return;
         if (i + 16 <= nonce_count) {
             // load and align data for SIMD
 
@@ -136,3 +141,18 @@ void find_best_deadline_avx512f(char *scoops, uint64_t nonce_count, char *gensig
         }
     }
 }
+
+void hash_lanes_avx512f(const char **inputs, size_t len, size_t lane_count, char **outputs) {
+    // unused lanes are deactivated by passing NULL
+    const char *in[MSHABAL512_VECTOR_SIZE] = {NULL};
+    char *out[MSHABAL512_VECTOR_SIZE] = {NULL};
+    for (size_t i = 0; i < lane_count && i < MSHABAL512_VECTOR_SIZE; i++) {
+        in[i] = inputs[i];
+        out[i] = outputs[i];
+    }
+
+    mshabal512_context x;
+    mshabal_init_avx512f(&x, 256);
+    mshabal_avx512f(&x, in[0], in[1], in[2], in[3], in[4], in[5], in[6], in[7], in[8], in[9], in[10], in[11], in[12], in[13], in[14], in[15], len);
+    mshabal_close_avx512f(&x, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, out[0], out[1], out[2], out[3], out[4], out[5], out[6], out[7], out[8], out[9], out[10], out[11], out[12], out[13], out[14], out[15]);
+}
@@ -5,8 +5,8 @@
 #include "mshabal_128_neon.h"
 #include "sph_shabal.h"
 
-mshabal128_context global_128;
-mshabal128_context_fast global_128_fast;
+static mshabal128_context global_128;
+static mshabal128_context_fast global_128_fast;
 
 void init_shabal_neon() {
     mshabal_init_neon(&global_128, 256);
@@ -17,7 +17,8 @@ void init_shabal_neon() {
 }
 
 void find_best_deadline_neon(char *scoops, uint64_t nonce_count, char *gensig,
-                             uint64_t *best_deadline, uint64_t *best_offset) {
+                             uint64_t *best_deadline, uint64_t *best_offset,
+                             const uint8_t *cancelled) {
     uint64_t d0 = 0, d1 = 0, d2 = 0, d3 = 0;
     char term[32];
     write_term(term);
@@ -44,7 +45,11 @@ void find_best_deadline_neon(char *scoops, uint64_t nonce_count, char *gensig,
         u2.words[i + 3 + 32] = *(mshabal_u32 *)(term + o);
     }
 
+    // the generation signature is the same for every batch, so absorb it once
+    mshabal_deadline_prefix_neon(&x, &u1);
+
     for (uint64_t i = 0; i < nonce_count;) {
+        if (IS_CANCELLED(cancelled)) return;
         if (i + 4 <= nonce_count) {
             // load and align data for SIMD
             for (uint64_t j = 0; j < 16 * MSHABAL128_VECTOR_SIZE / 2; j += MSHABAL128_VECTOR_SIZE) {
@@ -73,3 +78,18 @@ void find_best_deadline_neon(char *scoops, uint64_t nonce_count, char *gensig,
         }
     }
 }
+
+void hash_lanes_neon(const char **inputs, size_t len, size_t lane_count, char **outputs) {
+    // unused lanes are deactivated by passing NULL
+    const char *in[MSHABAL128_VECTOR_SIZE] = {NULL};
+    char *out[MSHABAL128_VECTOR_SIZE] = {NULL};
+    for (size_t i = 0; i < lane_count && i < MSHABAL128_VECTOR_SIZE; i++) {
+        in[i] = inputs[i];
+        out[i] = outputs[i];
+    }
+
+    mshabal128_context x;
+    mshabal_init_neon(&x, 256);
+    mshabal_neon(&x, in[0], in[1], in[2], in[3], len);
+    mshabal_close_neon(&x, 0, 0, 0, 0, 0, out[0], out[1], out[2], out[3]);
+}
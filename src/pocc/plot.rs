@@ -1,11 +1,13 @@
 use crate::pocc::shabal256_fast::shabal256_fast;
+use crate::cancel;
 use std::ptr::copy_nonoverlapping;
+use std::sync::atomic::AtomicU8;
 
 const HASH_SIZE: usize = 32;
 const HASH_CAP: usize = 4096;
-const NUM_SCOOPS: usize = 4096;
+pub const NUM_SCOOPS: usize = 4096;
 pub const SCOOP_SIZE: usize = 64;
-pub const NONCE_SIZE: usize = (NUM_SCOOPS * SCOOP_SIZE);
+pub const NONCE_SIZE: usize = NUM_SCOOPS * SCOOP_SIZE;
 const MESSAGE_SIZE: usize = 16;
 
 // cache:		    cache to save to
@@ -13,12 +15,14 @@ const MESSAGE_SIZE: usize = 16;
 // numeric_id:		numeric account id
 // loc_startnonce	nonce to start generation at
 // local_nonces: 	number of nonces to generate (count from 0)
+// cancelled:		flag polled between nonces, stops when non-zero
 pub fn noncegen_rust(
     cache: &mut [u8],
     numeric_id: u64,
     local_startnonce: u64,
     local_nonces: u64,
     poc_version: u8,
+    cancelled: Option<&AtomicU8>,
 ) {
     let numeric_id: [u32; 2] = unsafe { std::mem::transmute(numeric_id.to_be()) };
 
@@ -39,6 +43,7 @@ pub fn noncegen_rust(
     let mut hash_buffer = [0u8; HASH_SIZE];
 
     for n in 0..local_nonces {
+        if cancel::is_set(cancelled) { return; }
         let offset = n as usize * NONCE_SIZE;
         let buffer = &mut cache[offset..offset + NONCE_SIZE];
 
@@ -1,4 +1,4 @@
-use std::slice::from_raw_parts;
+use std::borrow::Cow;
 
 const A_INIT: [u32; 12] = [
     0x52F84552, 0xE54B7999, 0x2D8EE3EC, 0xB9645191, 0xE0078B86, 0xBB7C44C9, 0xD2B5C1CA, 0xB0D2EB8C,
@@ -16,35 +16,128 @@ const C_INIT: [u32; 16] = [
 ];
 
 pub fn shabal256_fast(data: &[u8], term: &[u32; 16]) -> [u8; 32] {
-    let mut a = A_INIT;
-    let mut b = B_INIT;
-    let mut c = C_INIT;
-    let mut w_high = 0u32;
-    let mut w_low = 1u32;
-    let mut num = data.len() >> 6;
-    let mut ptr = 0;
-    let data_ptr = data.as_ptr() as *const u32;
-    let data = unsafe { from_raw_parts(data_ptr, data.len() / 4) };
+    let mut state = Shabal256Midstate::initial();
+    state.absorb_blocks(&as_words(data));
+    return state.finish(term);
+}
+
+/// Shabal256 state after absorbing a fixed message prefix.
+///
+/// When many messages start with the same prefix (eg. the generation signature in front of every scoop)
+/// the prefix only needs to be absorbed once and the midstate can then finish each message with `hash`.
+/// Whole 64 byte blocks of the prefix are compressed, the words of a trailing partial block are added
+/// into the state ahead of time.
+#[derive(Debug, Clone)]
+pub struct Shabal256Midstate {
+    a: [u32; 12],
+    b: [u32; 16],
+    c: [u32; 16],
+    w_low: u32,
+    w_high: u32,
+    open_block: [u32; 16],
+    open_words: usize,
+}
+
+impl Shabal256Midstate {
+    /// Absorbs `prefix`, or returns `None` if its length is not a multiple of 4 bytes.
+    pub fn new(prefix: &[u8]) -> Option<Self> {
+        if !prefix.len().is_multiple_of(4) { return None; }
+        let words = as_words(prefix);
+        let whole_words = words.len() - words.len() % 16;
+        let mut state = Shabal256Midstate::initial();
+        state.absorb_blocks(&words[..whole_words]);
+
+        let open_words = words.len() - whole_words;
+        if open_words > 0 {
+            state.open_block[..open_words].copy_from_slice(&words[whole_words..]);
+            for (element, word) in state.b.iter_mut().zip(&words[whole_words..]) {
+                *element = element.wrapping_add(*word).rotate_left(17);
+            }
+            xor_w(&mut state.a, state.w_low, state.w_high);
+            state.open_words = open_words;
+        }
+        return Some(state);
+    }
+
+    /// Hashes the prefix followed by `data` and the termination block `term`.
+    ///
+    /// Like `shabal256_fast`, `data` has to complete the prefix's partial block and then consist of whole 64 byte blocks.
+    /// Returns `None` if `data` is too short to complete the partial block.
+    pub fn hash(&self, data: &[u8], term: &[u32; 16]) -> Option<[u8; 32]> {
+        let mut state = self.clone();
+        let words = as_words(data);
+        let mut rest = &words[..];
+        if state.open_words > 0 {
+            if rest.len() < 16 - state.open_words { return None; }
+            let (open_rest, blocks) = rest.split_at(16 - state.open_words);
+            let open_words = state.open_words;
+            state.open_block[open_words..].copy_from_slice(open_rest);
+            for (element, word) in state.b[open_words..].iter_mut().zip(open_rest) {
+                *element = element.wrapping_add(*word).rotate_left(17);
+            }
+            let block = state.open_block;
+            state.compress_rotated(&block);
+            rest = blocks;
+        }
+        state.absorb_blocks(rest);
+        return Some(state.finish(term));
+    }
 
-    while num > 0 {
-        input_block_add(&mut b, &data[ptr..]);
-        xor_w(&mut a, w_low, w_high);
-        apply_p(&mut a, &mut b, &c, &data[ptr..]);
-        input_block_sub(&mut c, &data[ptr..]);
-        swap_bc(&mut b, &mut c);
-        incr_w(&mut w_low, &mut w_high);
-        ptr = ptr.wrapping_add(16);
-        num = num.wrapping_sub(1);
+    fn initial() -> Self {
+        return Shabal256Midstate {
+            a: A_INIT,
+            b: B_INIT,
+            c: C_INIT,
+            w_high: 0,
+            w_low: 1,
+            open_block: [0; 16],
+            open_words: 0,
+        };
+    }
+
+    fn absorb_blocks(&mut self, data: &[u32]) {
+        for block in data.chunks_exact(16) {
+            input_block_add(&mut self.b, block);
+            xor_w(&mut self.a, self.w_low, self.w_high);
+            rotate_b(&mut self.b);
+            self.compress_rotated(block);
+        }
+    }
+
+    /// Finishes compressing `block` once it has been added to `b`, `b` rotated and `w` mixed into `a`.
+    #[inline(always)]
+    fn compress_rotated(&mut self, block: &[u32]) {
+        apply_p_rotated(&mut self.a, &mut self.b, &self.c, block);
+        input_block_sub(&mut self.c, block);
+        swap_bc(&mut self.b, &mut self.c);
+        incr_w(&mut self.w_low, &mut self.w_high);
     }
-    input_block_add(&mut b, term);
-    xor_w(&mut a, w_low, w_high);
-    apply_p(&mut a, &mut b, &c, term);
-    for _ in 0..3 {
-        swap_bc(&mut b, &mut c);
-        xor_w(&mut a, w_low, w_high);
-        apply_p(&mut a, &mut b, &c, term);
+
+    fn finish(mut self, term: &[u32; 16]) -> [u8; 32] {
+        let (a, b, c) = (&mut self.a, &mut self.b, &mut self.c);
+        input_block_add(b, term);
+        xor_w(a, self.w_low, self.w_high);
+        apply_p(a, b, c, term);
+        for _ in 0..3 {
+            swap_bc(b, c);
+            xor_w(a, self.w_low, self.w_high);
+            apply_p(a, b, c, term);
+        }
+        unsafe { *(b[8..16].as_ptr() as *const [u8; 32]) }
     }
-    unsafe { *(b[8..16].as_ptr() as *const [u8; 32]) }
+}
+
+/// Reads `data` as native endian words, copying it if it is not 4 byte aligned.
+fn as_words(data: &[u8]) -> Cow<'_, [u32]> {
+    return match unsafe { data.align_to::<u32>() } {
+        (&[], words, _) => Cow::Borrowed(words),
+        // callers' buffers are not guaranteed to be 4 byte aligned
+        _ => Cow::Owned(
+            data.chunks_exact(4)
+                .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+                .collect(),
+        ),
+    };
 }
 
 #[inline(always)]
@@ -69,9 +162,19 @@ fn xor_w(a: &mut [u32; 12], w_low: u32, w_high: u32) {
 
 #[inline(always)]
 fn apply_p(a: &mut [u32; 12], b: &mut [u32; 16], c: &[u32; 16], data: &[u32]) {
+    rotate_b(b);
+    apply_p_rotated(a, b, c, data);
+}
+
+#[inline(always)]
+fn rotate_b(b: &mut [u32; 16]) {
     for element in b.iter_mut() {
         *element = element.wrapping_shl(17) | element.wrapping_shr(15);
     }
+}
+
+#[inline(always)]
+fn apply_p_rotated(a: &mut [u32; 12], b: &mut [u32; 16], c: &[u32; 16], data: &[u32]) {
     perm(a, b, c, data);
     a[0] = a[0]
         .wrapping_add(c[11])
@@ -124,6 +227,7 @@ fn apply_p(a: &mut [u32; 12], b: &mut [u32; 16], c: &[u32; 16], data: &[u32]) {
 }
 
 #[inline(always)]
+#[allow(clippy::too_many_arguments)]
 fn perm_elt(
     a: &mut [u32; 12],
     b: &mut [u32; 16],
@@ -260,4 +364,46 @@ mod test {
         };
         assert_eq!(hash_b, TEST_B_RESULT);
     }
+
+    #[test]
+    fn midstate_matches_full_hash() {
+        let data: Vec<u8> = (0..192u32).map(|i| (i * 7) as u8).collect();
+        let mut term = [0u32; 16];
+        term[0] = 0x80;
+        for &prefix_len in [0, 32, 64, 100].iter() {
+            let midstate = Shabal256Midstate::new(&data[..prefix_len]).unwrap();
+            assert_eq!(midstate.hash(&data[prefix_len..], &term), Some(shabal256_fast(&data, &term)));
+        }
+    }
+
+    #[test]
+    fn midstate_rejects_partial_input() {
+        assert!(Shabal256Midstate::new(&[0u8; 30]).is_none());
+        let midstate = Shabal256Midstate::new(&[0u8; 32]).unwrap();
+        let term = [0u32; 16];
+        assert_eq!(midstate.hash(&[], &term), None);
+        assert_eq!(midstate.hash(&[0u8; 28], &term), None);
+        assert!(midstate.hash(&[0u8; 32], &term).is_some());
+    }
+
+    /// Hashing gensig prefixed scoops with and without the midstate,
+    /// run with `cargo test --release -- --ignored --nocapture bench_`.
+    #[test]
+    #[ignore]
+    fn bench_midstate() {
+        let rounds = 1_000_000;
+        let message = [0x5au8; 64];
+        let start = std::time::Instant::now();
+        for i in 0..rounds {
+            std::hint::black_box(shabal256_fast(std::hint::black_box(&message), &[i; 16]));
+        }
+        let full = start.elapsed();
+        let midstate = Shabal256Midstate::new(&message[..32]).unwrap();
+        let start = std::time::Instant::now();
+        for i in 0..rounds {
+            std::hint::black_box(midstate.hash(std::hint::black_box(&message[32..]), &[i; 16]).unwrap());
+        }
+        let prefixed = start.elapsed();
+        println!("shabal256_fast: {:?}, with midstate: {:?}", full, prefixed);
+    }
 }
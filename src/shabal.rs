@@ -1,45 +1,258 @@
-use shabal::{Shabal256, Digest};
+pub use shabal::{Digest, Shabal192, Shabal224, Shabal256, Shabal384, Shabal512};
+pub use shabal::digest::{Input, InvalidOutputSize, Reset, VariableOutput};
+use std::mem;
 use std::os::raw::c_void;
+use std::ptr;
 use std::slice;
 
+/// A Shabal hasher whose output size is chosen at runtime.
+///
+/// Use `VariableOutput::new` with an output size in bytes of 24, 28, 32, 48 or 64
+/// (Shabal192, Shabal224, Shabal256, Shabal384 and Shabal512 respectively).
+/// When the size is known at compile time the fixed size hashers re-exported from this module can be used directly.
+#[derive(Debug, Clone)]
+pub enum Shabal {
+    Shabal192(Shabal192),
+    Shabal224(Shabal224),
+    Shabal256(Shabal256),
+    Shabal384(Shabal384),
+    Shabal512(Shabal512),
+}
+
+impl Input for Shabal {
+    fn input<B: AsRef<[u8]>>(&mut self, data: B) {
+        match self {
+            Shabal::Shabal192(shabal) => Input::input(shabal, data),
+            Shabal::Shabal224(shabal) => Input::input(shabal, data),
+            Shabal::Shabal256(shabal) => Input::input(shabal, data),
+            Shabal::Shabal384(shabal) => Input::input(shabal, data),
+            Shabal::Shabal512(shabal) => Input::input(shabal, data),
+        }
+    }
+}
+
+impl Reset for Shabal {
+    fn reset(&mut self) {
+        match self {
+            Shabal::Shabal192(shabal) => Reset::reset(shabal),
+            Shabal::Shabal224(shabal) => Reset::reset(shabal),
+            Shabal::Shabal256(shabal) => Reset::reset(shabal),
+            Shabal::Shabal384(shabal) => Reset::reset(shabal),
+            Shabal::Shabal512(shabal) => Reset::reset(shabal),
+        }
+    }
+}
+
+impl VariableOutput for Shabal {
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        return match output_size {
+            24 => Ok(Shabal::Shabal192(Shabal192::new())),
+            28 => Ok(Shabal::Shabal224(Shabal224::new())),
+            32 => Ok(Shabal::Shabal256(Shabal256::new())),
+            48 => Ok(Shabal::Shabal384(Shabal384::new())),
+            64 => Ok(Shabal::Shabal512(Shabal512::new())),
+            _ => Err(InvalidOutputSize),
+        };
+    }
+
+    fn output_size(&self) -> usize {
+        return match self {
+            Shabal::Shabal192(_) => 24,
+            Shabal::Shabal224(_) => 28,
+            Shabal::Shabal256(_) => 32,
+            Shabal::Shabal384(_) => 48,
+            Shabal::Shabal512(_) => 64,
+        };
+    }
+
+    fn variable_result<F: FnOnce(&[u8])>(self, f: F) {
+        match self {
+            Shabal::Shabal192(shabal) => f(&shabal.result()),
+            Shabal::Shabal224(shabal) => f(&shabal.result()),
+            Shabal::Shabal256(shabal) => f(&shabal.result()),
+            Shabal::Shabal384(shabal) => f(&shabal.result()),
+            Shabal::Shabal512(shabal) => f(&shabal.result()),
+        }
+    }
+}
+
 pub fn shabal256_new() -> *mut c_void {
-    return Box::into_raw(Box::new(Shabal256::new())) as *mut c_void;
+    return new::<Shabal256>();
 }
 
 pub fn shabal256_destroy(shabal: *mut c_void) {
+    destroy::<Shabal256>(shabal);
+}
+
+pub fn shabal256_reset(shabal: *mut c_void) {
+    reset::<Shabal256>(shabal);
+}
+
+pub fn shabal256_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    update::<Shabal256>(shabal, data, offset, len);
+}
+
+pub fn shabal256_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
+    digest::<Shabal256>(shabal, buffer, offset);
+}
+
+pub fn shabal256_context_size() -> usize {
+    return mem::size_of::<Shabal256>();
+}
+
+pub fn shabal256_context_align() -> usize {
+    return mem::align_of::<Shabal256>();
+}
+
+pub fn shabal256_init_in_place(buffer: *mut c_void) {
+    init_in_place::<Shabal256>(buffer);
+}
+
+pub fn shabal256_clone(shabal: *const c_void, destination: *mut c_void) {
+    clone::<Shabal256>(shabal, destination);
+}
+
+pub fn shabal512_new() -> *mut c_void {
+    return new::<Shabal512>();
+}
+
+pub fn shabal512_destroy(shabal: *mut c_void) {
+    destroy::<Shabal512>(shabal);
+}
+
+pub fn shabal512_reset(shabal: *mut c_void) {
+    reset::<Shabal512>(shabal);
+}
+
+pub fn shabal512_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    update::<Shabal512>(shabal, data, offset, len);
+}
+
+pub fn shabal512_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
+    digest::<Shabal512>(shabal, buffer, offset);
+}
+
+fn new<D: Digest>() -> *mut c_void {
+    return Box::into_raw(Box::new(D::new())) as *mut c_void;
+}
+
+/// Writes a fresh hasher into caller owned memory.
+///
+/// The hashers own no heap memory, so the buffer can simply be released by the caller once it is done with it.
+fn init_in_place<D: Digest>(buffer: *mut c_void) {
+    if !buffer.is_null() && is_aligned::<D>(buffer) {
+        unsafe {
+            ptr::write(buffer as *mut D, D::new());
+        }
+    }
+}
+
+/// Copies the state of `shabal` into `destination`, which may be another hasher or uninitialized memory.
+fn clone<D: Digest + Clone>(shabal: *const c_void, destination: *mut c_void) {
+    if !shabal.is_null() && !destination.is_null() && is_aligned::<D>(destination) {
+        unsafe {
+            let shabal_borrowed = &*(shabal as *const D);
+            ptr::write(destination as *mut D, shabal_borrowed.clone());
+        }
+    }
+}
+
+pub(crate) fn is_aligned<D>(buffer: *const c_void) -> bool {
+    return (buffer as *const D).is_aligned();
+}
+
+fn destroy<D: Digest>(shabal: *mut c_void) {
     if !shabal.is_null() {
         unsafe {
-            // Let it fall out of scope naturally once it is unboxed
-            Box::from_raw(shabal as *mut Shabal256);
+            drop(Box::from_raw(shabal as *mut D));
         }
     }
 }
 
-pub fn shabal256_reset(shabal: *mut c_void) {
+fn reset<D: Digest>(shabal: *mut c_void) {
     if !shabal.is_null() {
         unsafe {
-            let shabal_borrowed = &mut *(shabal as *mut Shabal256);
+            let shabal_borrowed = &mut *(shabal as *mut D);
             shabal_borrowed.reset();
         }
     }
 }
 
-pub fn shabal256_update(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
-    if !shabal.is_null() {
+fn update<D: Digest>(shabal: *mut c_void, data: *const u8, offset: usize, len: usize) {
+    if !shabal.is_null() && !data.is_null() {
         unsafe {
-            let array = slice::from_raw_parts(data.add(offset), len as usize);
-            let shabal_borrowed = &mut *(shabal as *mut Shabal256);
+            let array = slice::from_raw_parts(data.add(offset), len);
+            let shabal_borrowed = &mut *(shabal as *mut D);
             shabal_borrowed.input(array);
         }
     }
 }
 
-pub fn shabal256_digest(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
-    if !shabal.is_null() {
+fn digest<D: Digest>(shabal: *mut c_void, buffer: *mut u8, offset: usize) {
+    if !shabal.is_null() && !buffer.is_null() {
         unsafe {
-            let array = slice::from_raw_parts_mut(buffer.add(offset), 32);
-            let shabal_borrowed = &mut *(shabal as *mut Shabal256);
+            let array = slice::from_raw_parts_mut(buffer.add(offset), D::output_size());
+            let shabal_borrowed = &mut *(shabal as *mut D);
             array.copy_from_slice(shabal_borrowed.result_reset().as_slice());
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn variable_output_matches_fixed_hashers() {
+        let message = b"helloworld";
+        let hash = |size: usize| {
+            let mut shabal = Shabal::new(size).unwrap();
+            shabal.input(&message[..4]);
+            shabal.input(&message[4..]);
+            assert_eq!(shabal.output_size(), size);
+            let mut result = Vec::new();
+            shabal.variable_result(|bytes| result.extend_from_slice(bytes));
+            result
+        };
+        assert_eq!(hash(24), Shabal192::digest(message).to_vec());
+        assert_eq!(hash(28), Shabal224::digest(message).to_vec());
+        assert_eq!(hash(32), Shabal256::digest(message).to_vec());
+        assert_eq!(hash(48), Shabal384::digest(message).to_vec());
+        assert_eq!(hash(64), Shabal512::digest(message).to_vec());
+        assert!(Shabal::new(20).is_err());
+    }
+
+    #[test]
+    fn shabal256_known_answer() {
+        let mut shabal = Shabal::new(32).unwrap();
+        shabal.input(b"hello");
+        shabal.reset();
+        shabal.input(b"helloworld");
+        let mut result = Vec::new();
+        shabal.variable_result(|bytes| result.extend_from_slice(bytes));
+        assert_eq!(result, [
+            0xd9, 0x45, 0xde, 0xe2, 0x1f, 0xfc, 0xa2, 0x3a, 0xc2, 0x32, 0x76, 0x3a, 0xa9, 0xca, 0xc6, 0xc1,
+            0x58, 0x05, 0xf1, 0x44, 0xdb, 0x9d, 0x6c, 0x97, 0x39, 0x54, 0x37, 0xe0, 0x1c, 0x85, 0x95, 0xa8,
+        ]);
+    }
+
+    #[test]
+    fn in_place_contexts_fork_midstates() {
+        let mut storage = [0u64; 64];
+        assert!(shabal256_context_size() <= mem::size_of_val(&storage));
+        let context = storage.as_mut_ptr() as *mut c_void;
+        let fork = unsafe { (storage.as_mut_ptr() as *mut u8).add(256) } as *mut c_void;
+
+        shabal256_init_in_place(context);
+        shabal256_update(context, b"hello".as_ptr(), 0, 5);
+        shabal256_clone(context, fork);
+        shabal256_update(context, b"world".as_ptr(), 0, 5);
+        shabal256_update(fork, b" there".as_ptr(), 0, 6);
+
+        let mut hash = [0u8; 32];
+        shabal256_digest(context, hash.as_mut_ptr(), 0);
+        assert_eq!(&hash[..], &Shabal256::digest(b"helloworld")[..]);
+        shabal256_digest(fork, hash.as_mut_ptr(), 0);
+        assert_eq!(&hash[..], &Shabal256::digest(b"hello there")[..]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Signing and verifying whole messages, such as transaction bytes.
+//!
+//! EC-KCDSA signs the SHA-256 hash of a message. These functions do the hashing, either in one go or,
+//! through `MessageHasher`, over a message that arrives in pieces.
+
+use crate::curve25519;
+use crate::keys::PrivateKey;
+use sha2::{Digest, Sha256};
+
+/// Signs `message` with `private_key`, returning the 64 byte signature.
+pub fn sign_message(private_key: &PrivateKey, message: &[u8]) -> [u8; 64] {
+    let mut hasher = MessageHasher::new();
+    hasher.update(message);
+    return hasher.sign(private_key);
+}
+
+/// Whether `signature` is the signature of `message` by the owner of `public_key`.
+///
+/// With `enforce_canonical` a public key or signature which is not in canonical form is rejected.
+pub fn verify_message(public_key: &[u8; 32], signature: &[u8; 64], message: &[u8], enforce_canonical: bool) -> bool {
+    let mut hasher = MessageHasher::new();
+    hasher.update(message);
+    return hasher.verify(public_key, signature, enforce_canonical);
+}
+
+/// Whether the first half of `signature` is reduced modulo the group order, as nodes require.
+///
+/// Adding the group order to it gives another valid signature of the same message, so only the reduced one is accepted.
+pub fn is_canonical_signature(signature: &[u8; 64]) -> bool {
+    return curve25519::is_canonical_signature(signature);
+}
+
+/// Whether `public_key` is reduced modulo 2^255 - 19, as nodes require.
+pub fn is_canonical_public_key(public_key: &[u8; 32]) -> bool {
+    return curve25519::is_canonical_public_key(public_key);
+}
+
+/// The canonical form of `signature`, which verifies as `signature` does for any public key from key generation.
+pub fn canonical_signature(signature: &[u8; 64]) -> [u8; 64] {
+    let mut canonical = [0u8; 64];
+    curve25519::canonicalize_signature(signature, &mut canonical);
+    return canonical;
+}
+
+/// The canonical form of `public_key`, which verifies the same signatures.
+///
+/// Account ids are hashes of the public key bytes, so the canonical form belongs to a different account id.
+pub fn canonical_public_key(public_key: &[u8; 32]) -> [u8; 32] {
+    let mut canonical = [0u8; 32];
+    curve25519::canonicalize_public_key(public_key, &mut canonical);
+    return canonical;
+}
+
+/// Hashes a message piece by piece, then signs or verifies it.
+///
+/// Signing or verifying the concatenation of the pieces is the same as `sign_message` or `verify_message`.
+#[derive(Debug, Clone, Default)]
+pub struct MessageHasher(Sha256);
+
+impl MessageHasher {
+    pub fn new() -> Self {
+        return MessageHasher(Sha256::new());
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.input(data);
+    }
+
+    pub fn sign(self, private_key: &PrivateKey) -> [u8; 64] {
+        let mut signature = [0u8; 64];
+        curve25519::sign(private_key.as_bytes(), &self.message_sha256(), &mut signature);
+        return signature;
+    }
+
+    pub fn verify(self, public_key: &[u8; 32], signature: &[u8; 64], enforce_canonical: bool) -> bool {
+        return curve25519::verify(public_key, signature, &self.message_sha256(), enforce_canonical);
+    }
+
+    /// The SHA-256 hash of the message so far, as passed to `curve25519_sign()` and `curve25519_verify()`.
+    pub fn message_sha256(self) -> [u8; 32] {
+        let mut message_sha256 = [0u8; 32];
+        message_sha256.copy_from_slice(&self.0.result());
+        return message_sha256;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn message_signatures_match_digest_signatures() {
+        let private_key = PrivateKey::from_bytes(&[0x42; 32]);
+        let public_key = private_key.public_key();
+        let message: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+
+        let mut message_sha256 = [0u8; 32];
+        message_sha256.copy_from_slice(&Sha256::digest(&message));
+        let signature = sign_message(&private_key, &message);
+        assert_eq!(signature[..], private_key.sign(&message_sha256)[..]);
+        assert!(verify_message(&public_key, &signature, &message, true));
+        assert!(!verify_message(&public_key, &signature, &message[1..], true));
+
+        let mut hasher = MessageHasher::new();
+        for chunk in message.chunks(77) {
+            hasher.update(chunk);
+        }
+        assert!(hasher.clone().verify(&public_key, &signature, true));
+        assert_eq!(hasher.sign(&private_key)[..], signature[..]);
+    }
+
+    #[test]
+    fn canonical_forms() {
+        let private_key = PrivateKey::from_bytes(&[0x42; 32]);
+        let public_key = private_key.public_key();
+        let signature = sign_message(&private_key, b"message");
+        assert!(is_canonical_signature(&signature) && is_canonical_public_key(&public_key));
+        assert_eq!(canonical_signature(&signature)[..], signature[..]);
+        assert_eq!(canonical_public_key(&public_key), public_key);
+
+        // the signature plus the group order, which only verifies when canonical form is not enforced
+        let mut malleated = signature;
+        let mut carry = 0u16;
+        for (byte, order_byte) in malleated[..32].iter_mut().zip([
+            237u8, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+        ].iter()) {
+            carry += *byte as u16 + *order_byte as u16;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        assert!(!is_canonical_signature(&malleated));
+        assert!(verify_message(&public_key, &malleated, b"message", false));
+        assert!(!verify_message(&public_key, &malleated, b"message", true));
+        assert_eq!(canonical_signature(&malleated)[..], signature[..]);
+    }
+}
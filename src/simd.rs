@@ -1,6 +1,9 @@
 use once_cell::sync::Lazy;
 
 #[derive(Debug, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+// variants are only constructed when their cargo feature is enabled
+#[allow(dead_code)]
 pub enum SimdExtension {
     AVX512f,
     AVX2,
@@ -10,6 +13,18 @@ pub enum SimdExtension {
     NONE,
 }
 
+impl SimdExtension {
+    /// Number of messages the extension's Shabal kernels hash in parallel.
+    pub fn lanes(&self) -> usize {
+        return match self {
+            SimdExtension::AVX512f => 16,
+            SimdExtension::AVX2 => 8,
+            SimdExtension::AVX | SimdExtension::SSE2 | SimdExtension::NEON => 4,
+            SimdExtension::NONE => 1,
+        };
+    }
+}
+
 pub static SUPPORTED_SIMD_EXTENSION: Lazy<SimdExtension> = Lazy::new(|| {
     #[cfg(feature = "simd")] {
         if is_x86_feature_detected!("avx512f") {
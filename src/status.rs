@@ -0,0 +1,94 @@
+use std::panic::{self, AssertUnwindSafe};
+
+/// Result of the checked C ABI functions.
+///
+/// `libshabal_status_string()` gives a human readable description of each status.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum libshabal_status {
+    OK = 0,
+    NULL_POINTER = 1,
+    BUFFER_TOO_SMALL = 2,
+    INVALID_ARGUMENT = 3,
+    NON_CANONICAL = 4,
+    VERIFICATION_FAILED = 5,
+    CANCELLED = 6,
+    INTERNAL_ERROR = 7,
+    INVALID_ADDRESS = 8,
+    DECRYPTION_FAILED = 9,
+}
+
+impl libshabal_status {
+    /// The status with the value `status`, or `None` for a value which is not a status.
+    pub fn from_u32(status: u32) -> Option<Self> {
+        return match status {
+            0 => Some(libshabal_status::OK),
+            1 => Some(libshabal_status::NULL_POINTER),
+            2 => Some(libshabal_status::BUFFER_TOO_SMALL),
+            3 => Some(libshabal_status::INVALID_ARGUMENT),
+            4 => Some(libshabal_status::NON_CANONICAL),
+            5 => Some(libshabal_status::VERIFICATION_FAILED),
+            6 => Some(libshabal_status::CANCELLED),
+            7 => Some(libshabal_status::INTERNAL_ERROR),
+            8 => Some(libshabal_status::INVALID_ADDRESS),
+            9 => Some(libshabal_status::DECRYPTION_FAILED),
+            _ => None,
+        };
+    }
+
+    pub fn description(self) -> &'static str {
+        return match self {
+            libshabal_status::OK => "OK\0",
+            libshabal_status::NULL_POINTER => "A required pointer was null\0",
+            libshabal_status::BUFFER_TOO_SMALL => "A buffer was too small for the requested operation\0",
+            libshabal_status::INVALID_ARGUMENT => "An argument was outside of its valid range\0",
+            libshabal_status::NON_CANONICAL => "A public key or signature was not in canonical form\0",
+            libshabal_status::VERIFICATION_FAILED => "The signature did not match the public key and message\0",
+            libshabal_status::CANCELLED => "The operation was cancelled\0",
+            libshabal_status::INTERNAL_ERROR => "An unexpected internal error occurred\0",
+            libshabal_status::INVALID_ADDRESS => "The address was malformed or its checksum did not match\0",
+            libshabal_status::DECRYPTION_FAILED => "The data could not be decrypted with the given keys and nonce\0",
+        };
+    }
+}
+
+/// Runs `body`, returning `fallback` instead of unwinding into the caller if it panics.
+///
+/// Unwinding across an `extern "C"` boundary is undefined behaviour, so every exported function goes through this.
+pub(crate) fn catch_panic<T, F: FnOnce() -> T>(fallback: T, body: F) -> T {
+    return panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(fallback);
+}
+
+/// Runs the body of a checked function, converting its result into a status.
+///
+/// A panic inside `body` is reported as `INTERNAL_ERROR`.
+pub(crate) fn run<F: FnOnce() -> Result<(), libshabal_status>>(body: F) -> libshabal_status {
+    return match catch_panic(Err(libshabal_status::INTERNAL_ERROR), body) {
+        Ok(()) => libshabal_status::OK,
+        Err(status) => status,
+    };
+}
+
+pub(crate) fn check_not_null<T>(ptr: *const T) -> Result<(), libshabal_status> {
+    return if ptr.is_null() { Err(libshabal_status::NULL_POINTER) } else { Ok(()) };
+}
+
+/// Checks that `len` bytes of `buffer` have room for `needed` bytes starting from `offset`.
+pub(crate) fn check_buffer(buffer: *const u8, len: usize, offset: usize, needed: usize) -> Result<(), libshabal_status> {
+    check_not_null(buffer)?;
+    return match offset.checked_add(needed) {
+        Some(end) if end <= len => Ok(()),
+        _ => Err(libshabal_status::BUFFER_TOO_SMALL),
+    };
+}
+
+/// Checks a fixed size input such as a key, signature or hash.
+pub(crate) fn check_input(input: *const u8, len: usize, expected: usize) -> Result<(), libshabal_status> {
+    check_not_null(input)?;
+    return if len == expected { Ok(()) } else { Err(libshabal_status::INVALID_ARGUMENT) };
+}
+
+pub(crate) fn check_poc_version(poc_version: u8) -> Result<(), libshabal_status> {
+    return if poc_version == 1 || poc_version == 2 { Ok(()) } else { Err(libshabal_status::INVALID_ARGUMENT) };
+}
@@ -0,0 +1,515 @@
+//! Burst transactions: building, serializing, signing and parsing their bytes.
+//!
+//! The bytes are those the reference node signs and broadcasts (transaction version 1, little endian):
+//!
+//! | bytes | field |
+//! |---|---|
+//! | 1 | type |
+//! | 1 | version << 4, subtype |
+//! | 4 | timestamp, seconds since the Burst epoch |
+//! | 2 | deadline in minutes |
+//! | 32 | sender public key |
+//! | 8 | recipient id, 0 for types without a recipient |
+//! | 8 | amount in NQT |
+//! | 8 | fee in NQT |
+//! | 32 | referenced transaction full hash or zeros |
+//! | 64 | signature, zeros while signing |
+//! | 4 | flags, which appendices follow the attachment |
+//! | 4 | EC block height |
+//! | 8 | EC block id |
+//!
+//! followed by the attachment of the transaction type and the appendices, each of which starts with its version byte
+//! unless it is version 0. The transaction is signed over these bytes with the signature zeroed.
+
+use crate::account;
+use crate::keys::PrivateKey;
+use crate::signature;
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Unix time of the Burst genesis block, from which transaction timestamps are counted.
+pub const BURST_EPOCH: u64 = 1407722400;
+
+/// Size of a reward recipient assignment transaction in bytes.
+pub const REWARD_RECIPIENT_ASSIGNMENT_SIZE: usize = 177;
+
+/// Default deadline of a transaction, in minutes.
+pub const DEFAULT_DEADLINE: u16 = 1440;
+
+const VERSION: u8 = 1;
+const HEADER_SIZE: usize = 176;
+const SIGNATURE_OFFSET: usize = 96;
+const FLAG_MESSAGE: u32 = 1;
+const FLAG_PUBLIC_KEY_ANNOUNCEMENT: u32 = 4;
+const TEXT_MESSAGE: u32 = 0x8000_0000;
+
+/// The type of a transaction along with the data specific to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attachment {
+    /// Sends the amount to the recipient.
+    OrdinaryPayment,
+    /// Carries a message to the recipient.
+    ArbitraryMessage,
+    /// Sends the block rewards of the sender to the recipient, usually a pool, from 4 blocks on.
+    RewardRecipientAssignment,
+    /// Commits `amount_nqt` of the sender's balance to mining.
+    CommitmentAdd { amount_nqt: u64 },
+    /// Releases `amount_nqt` of the sender's committed balance.
+    CommitmentRemove { amount_nqt: u64 },
+}
+
+impl Attachment {
+    fn type_and_subtype(&self) -> (u8, u8) {
+        return match self {
+            Attachment::OrdinaryPayment => (0, 0),
+            Attachment::ArbitraryMessage => (1, 0),
+            Attachment::RewardRecipientAssignment => (20, 0),
+            Attachment::CommitmentAdd { .. } => (20, 1),
+            Attachment::CommitmentRemove { .. } => (20, 2),
+        };
+    }
+
+    fn has_recipient(&self) -> bool {
+        return !matches!(self, Attachment::CommitmentAdd { .. } | Attachment::CommitmentRemove { .. });
+    }
+
+    fn write_to(&self, bytes: &mut Vec<u8>) {
+        match self {
+            // version 0 attachments, which have no bytes at all
+            Attachment::OrdinaryPayment | Attachment::ArbitraryMessage => {}
+            Attachment::RewardRecipientAssignment => bytes.push(1),
+            Attachment::CommitmentAdd { amount_nqt } | Attachment::CommitmentRemove { amount_nqt } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&amount_nqt.to_le_bytes());
+            }
+        }
+    }
+
+    fn read_from(transaction_type: u8, subtype: u8, reader: &mut Reader) -> Result<Attachment, TransactionError> {
+        return match (transaction_type, subtype) {
+            (0, 0) => Ok(Attachment::OrdinaryPayment),
+            (1, 0) => Ok(Attachment::ArbitraryMessage),
+            (20, 0) => {
+                reader.appendix_version()?;
+                Ok(Attachment::RewardRecipientAssignment)
+            }
+            (20, 1) => {
+                reader.appendix_version()?;
+                Ok(Attachment::CommitmentAdd { amount_nqt: reader.u64()? })
+            }
+            (20, 2) => {
+                reader.appendix_version()?;
+                Ok(Attachment::CommitmentRemove { amount_nqt: reader.u64()? })
+            }
+            _ => Err(TransactionError::UnsupportedType { transaction_type, subtype }),
+        };
+    }
+}
+
+/// A plain message appendix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub bytes: Vec<u8>,
+    /// Whether `bytes` is UTF-8 text rather than binary data.
+    pub is_text: bool,
+}
+
+impl Message {
+    pub fn text(text: &str) -> Self {
+        return Message { bytes: text.as_bytes().to_vec(), is_text: true };
+    }
+
+    pub fn binary(bytes: &[u8]) -> Self {
+        return Message { bytes: bytes.to_vec(), is_text: false };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionError {
+    /// The bytes end before the transaction does.
+    Truncated,
+    /// There are bytes left after the transaction.
+    TrailingBytes,
+    /// Only version 1 transactions, and version 1 appendices, are supported.
+    UnsupportedVersion(u8),
+    UnsupportedType { transaction_type: u8, subtype: u8 },
+    /// The flags announce an appendix which is not supported, such as an encrypted message.
+    UnsupportedAppendix(u32),
+    /// A message is longer than the bytes hold.
+    InvalidMessage,
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            TransactionError::Truncated => write!(f, "the transaction bytes are truncated"),
+            TransactionError::TrailingBytes => write!(f, "unexpected bytes after the transaction"),
+            TransactionError::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            TransactionError::UnsupportedType { transaction_type, subtype } => {
+                write!(f, "unsupported transaction type {} subtype {}", transaction_type, subtype)
+            }
+            TransactionError::UnsupportedAppendix(flags) => write!(f, "unsupported appendices, flags {:#x}", flags),
+            TransactionError::InvalidMessage => write!(f, "invalid message length"),
+        };
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub attachment: Attachment,
+    /// Seconds since `BURST_EPOCH`.
+    pub timestamp: u32,
+    /// Minutes after `timestamp` during which the transaction can be included in a block.
+    pub deadline: u16,
+    pub sender_public_key: [u8; 32],
+    /// Ignored, and written as 0, for types without a recipient.
+    pub recipient_id: u64,
+    pub amount_nqt: u64,
+    pub fee_nqt: u64,
+    pub referenced_transaction_full_hash: Option<[u8; 32]>,
+    pub signature: Option<[u8; 64]>,
+    /// Height and id of the economic clustering block, a recent block the transaction is only valid on top of.
+    ///
+    /// Nodes reject transactions whose EC block is not on their chain, so these come from a node (`getECBlock`).
+    pub ec_block_height: u32,
+    pub ec_block_id: u64,
+    pub message: Option<Message>,
+    /// Announces the public key of the recipient, to set it for an account which has none yet.
+    pub recipient_public_key: Option<[u8; 32]>,
+}
+
+impl Transaction {
+    /// An unsigned transaction with the default deadline and no amount, recipient, EC block or appendices.
+    pub fn new(attachment: Attachment, sender_public_key: [u8; 32], fee_nqt: u64, timestamp: u32) -> Self {
+        return Transaction {
+            attachment,
+            timestamp,
+            deadline: DEFAULT_DEADLINE,
+            sender_public_key,
+            recipient_id: 0,
+            amount_nqt: 0,
+            fee_nqt,
+            referenced_transaction_full_hash: None,
+            signature: None,
+            ec_block_height: 0,
+            ec_block_id: 0,
+            message: None,
+            recipient_public_key: None,
+        };
+    }
+
+    pub fn ordinary_payment(sender_public_key: [u8; 32], recipient_id: u64, amount_nqt: u64, fee_nqt: u64, timestamp: u32) -> Self {
+        let mut transaction = Transaction::new(Attachment::OrdinaryPayment, sender_public_key, fee_nqt, timestamp);
+        transaction.recipient_id = recipient_id;
+        transaction.amount_nqt = amount_nqt;
+        return transaction;
+    }
+
+    pub fn arbitrary_message(sender_public_key: [u8; 32], recipient_id: u64, message: Message, fee_nqt: u64, timestamp: u32) -> Self {
+        let mut transaction = Transaction::new(Attachment::ArbitraryMessage, sender_public_key, fee_nqt, timestamp);
+        transaction.recipient_id = recipient_id;
+        transaction.message = Some(message);
+        return transaction;
+    }
+
+    pub fn reward_recipient_assignment(sender_public_key: [u8; 32], recipient_id: u64, fee_nqt: u64, timestamp: u32) -> Self {
+        let mut transaction = Transaction::new(Attachment::RewardRecipientAssignment, sender_public_key, fee_nqt, timestamp);
+        transaction.recipient_id = recipient_id;
+        return transaction;
+    }
+
+    pub fn commitment_add(sender_public_key: [u8; 32], amount_nqt: u64, fee_nqt: u64, timestamp: u32) -> Self {
+        return Transaction::new(Attachment::CommitmentAdd { amount_nqt }, sender_public_key, fee_nqt, timestamp);
+    }
+
+    pub fn commitment_remove(sender_public_key: [u8; 32], amount_nqt: u64, fee_nqt: u64, timestamp: u32) -> Self {
+        return Transaction::new(Attachment::CommitmentRemove { amount_nqt }, sender_public_key, fee_nqt, timestamp);
+    }
+
+    /// The transaction bytes, with a zero signature if the transaction is not signed.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (transaction_type, subtype) = self.attachment.type_and_subtype();
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.push(transaction_type);
+        bytes.push((VERSION << 4) | subtype);
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        bytes.extend_from_slice(&self.deadline.to_le_bytes());
+        bytes.extend_from_slice(&self.sender_public_key);
+        let recipient_id = if self.attachment.has_recipient() { self.recipient_id } else { 0 };
+        bytes.extend_from_slice(&recipient_id.to_le_bytes());
+        bytes.extend_from_slice(&self.amount_nqt.to_le_bytes());
+        bytes.extend_from_slice(&self.fee_nqt.to_le_bytes());
+        bytes.extend_from_slice(&self.referenced_transaction_full_hash.unwrap_or([0u8; 32]));
+        bytes.extend_from_slice(&self.signature.unwrap_or([0u8; 64]));
+        bytes.extend_from_slice(&self.flags().to_le_bytes());
+        bytes.extend_from_slice(&self.ec_block_height.to_le_bytes());
+        bytes.extend_from_slice(&self.ec_block_id.to_le_bytes());
+
+        self.attachment.write_to(&mut bytes);
+        if let Some(message) = &self.message {
+            bytes.push(1);
+            let length = message.bytes.len() as u32 | if message.is_text { TEXT_MESSAGE } else { 0 };
+            bytes.extend_from_slice(&length.to_le_bytes());
+            bytes.extend_from_slice(&message.bytes);
+        }
+        if let Some(public_key) = &self.recipient_public_key {
+            bytes.push(1);
+            bytes.extend_from_slice(public_key);
+        }
+        return bytes;
+    }
+
+    /// The bytes which are signed: `to_bytes()` with a zero signature.
+    pub fn unsigned_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        bytes[SIGNATURE_OFFSET..SIGNATURE_OFFSET + 64].copy_from_slice(&[0u8; 64]);
+        return bytes;
+    }
+
+    /// Signs the transaction with the private key of its sender, replacing any previous signature.
+    pub fn sign(&mut self, private_key: &PrivateKey) {
+        self.signature = Some(signature::sign_message(private_key, &self.unsigned_bytes()));
+    }
+
+    /// Whether the transaction is signed by the owner of `sender_public_key`, with a canonical signature.
+    pub fn verify(&self) -> bool {
+        return match &self.signature {
+            Some(signature) => signature::verify_message(&self.sender_public_key, signature, &self.unsigned_bytes(), true),
+            None => false,
+        };
+    }
+
+    /// SHA-256 of the unsigned bytes followed by SHA-256 of the signature, or `None` if the transaction is not signed.
+    pub fn full_hash(&self) -> Option<[u8; 32]> {
+        let signature = self.signature?;
+        let mut sha256 = Sha256::new();
+        sha256.input(self.unsigned_bytes());
+        sha256.input(Sha256::digest(&signature));
+        let mut full_hash = [0u8; 32];
+        full_hash.copy_from_slice(&sha256.result());
+        return Some(full_hash);
+    }
+
+    /// The transaction id: the first 8 bytes of the full hash, little endian.
+    pub fn id(&self) -> Option<u64> {
+        return self.full_hash().map(|full_hash| u64::from_le_bytes(full_hash[..8].try_into().unwrap()));
+    }
+
+    /// Parses transaction bytes, signed or not. A zero signature is read as no signature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Transaction, TransactionError> {
+        let mut reader = Reader { bytes, position: 0 };
+        let transaction_type = reader.u8()?;
+        let version_and_subtype = reader.u8()?;
+        if version_and_subtype >> 4 != VERSION { return Err(TransactionError::UnsupportedVersion(version_and_subtype >> 4)); }
+        let subtype = version_and_subtype & 0x0F;
+        let timestamp = reader.u32()?;
+        let deadline = u16::from_le_bytes(reader.array()?);
+        let sender_public_key = reader.array()?;
+        let recipient_id = reader.u64()?;
+        let amount_nqt = reader.u64()?;
+        let fee_nqt = reader.u64()?;
+        let referenced_transaction_full_hash: [u8; 32] = reader.array()?;
+        let signature: [u8; 64] = reader.array()?;
+        let flags = reader.u32()?;
+        let ec_block_height = reader.u32()?;
+        let ec_block_id = reader.u64()?;
+
+        let attachment = Attachment::read_from(transaction_type, subtype, &mut reader)?;
+        if flags & !(FLAG_MESSAGE | FLAG_PUBLIC_KEY_ANNOUNCEMENT) != 0 { return Err(TransactionError::UnsupportedAppendix(flags)); }
+        let mut message = None;
+        if flags & FLAG_MESSAGE != 0 {
+            reader.appendix_version()?;
+            let length = reader.u32()?;
+            let message_length = (length & !TEXT_MESSAGE) as usize;
+            if message_length > reader.remaining() { return Err(TransactionError::InvalidMessage); }
+            message = Some(Message { bytes: reader.take(message_length)?.to_vec(), is_text: length & TEXT_MESSAGE != 0 });
+        }
+        let mut recipient_public_key = None;
+        if flags & FLAG_PUBLIC_KEY_ANNOUNCEMENT != 0 {
+            reader.appendix_version()?;
+            recipient_public_key = Some(reader.array()?);
+        }
+        if reader.remaining() != 0 { return Err(TransactionError::TrailingBytes); }
+
+        return Ok(Transaction {
+            attachment,
+            timestamp,
+            deadline,
+            sender_public_key,
+            recipient_id,
+            amount_nqt,
+            fee_nqt,
+            referenced_transaction_full_hash: if referenced_transaction_full_hash == [0u8; 32] { None } else { Some(referenced_transaction_full_hash) },
+            signature: if signature == [0u8; 64] { None } else { Some(signature) },
+            ec_block_height,
+            ec_block_id,
+            message,
+            recipient_public_key,
+        });
+    }
+
+    fn flags(&self) -> u32 {
+        let mut flags = 0;
+        if self.message.is_some() { flags |= FLAG_MESSAGE; }
+        if self.recipient_public_key.is_some() { flags |= FLAG_PUBLIC_KEY_ANNOUNCEMENT; }
+        return flags;
+    }
+}
+
+/// Builds and signs the transaction which makes `pool_account_id` the reward recipient of the account of `passphrase`,
+/// as pool miners have to before mining. Nothing is sent anywhere: broadcast `to_bytes()` by any means.
+///
+/// `ec_block_height` and `ec_block_id` are a recent block of the chain the transaction is meant for, see `Transaction`.
+pub fn assign_reward_recipient(passphrase: &[u8], pool_account_id: u64, fee_nqt: u64, timestamp: u32, ec_block_height: u32, ec_block_id: u64) -> Transaction {
+    let keypair = account::keypair_from_passphrase(passphrase);
+    let mut transaction = Transaction::reward_recipient_assignment(keypair.public_key, pool_account_id, fee_nqt, timestamp);
+    transaction.ec_block_height = ec_block_height;
+    transaction.ec_block_id = ec_block_id;
+    transaction.sign(&keypair.agreement_key);
+    return transaction;
+}
+
+/// The current time as a transaction timestamp.
+pub fn current_timestamp() -> u32 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|since_epoch| since_epoch.as_secs()).unwrap_or(0);
+    return now.saturating_sub(BURST_EPOCH) as u32;
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TransactionError> {
+        if len > self.remaining() { return Err(TransactionError::Truncated); }
+        let taken = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        return Ok(taken);
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], TransactionError> {
+        return Ok(self.take(N)?.try_into().unwrap());
+    }
+
+    fn u8(&mut self) -> Result<u8, TransactionError> {
+        return Ok(self.take(1)?[0]);
+    }
+
+    fn u32(&mut self) -> Result<u32, TransactionError> {
+        return Ok(u32::from_le_bytes(self.array()?));
+    }
+
+    fn u64(&mut self) -> Result<u64, TransactionError> {
+        return Ok(u64::from_le_bytes(self.array()?));
+    }
+
+    fn appendix_version(&mut self) -> Result<(), TransactionError> {
+        let version = self.u8()?;
+        return if version == 1 { Ok(()) } else { Err(TransactionError::UnsupportedVersion(version)) };
+    }
+
+    fn remaining(&self) -> usize {
+        return self.bytes.len() - self.position;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn payment_bytes_layout() {
+        let keypair = account::keypair_from_passphrase(b"correct horse battery staple");
+        let mut payment = Transaction::ordinary_payment(keypair.public_key, 0x0102030405060708, 100_000_000, 735_000, 0x11223344);
+        payment.ec_block_height = 500_000;
+        payment.ec_block_id = 0xAABBCCDDEEFF0011;
+
+        let bytes = payment.to_bytes();
+        assert_eq!(bytes.len(), HEADER_SIZE);
+        assert_eq!(bytes[..8], [0, 0x10, 0x44, 0x33, 0x22, 0x11, 0xA0, 0x05]);
+        assert_eq!(bytes[8..40], keypair.public_key);
+        assert_eq!(bytes[40..48], [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(bytes[48..56], 100_000_000u64.to_le_bytes());
+        assert_eq!(bytes[56..64], 735_000u64.to_le_bytes());
+        assert!(bytes[64..164].iter().all(|&byte| byte == 0));
+        assert_eq!(bytes[164..168], 500_000u32.to_le_bytes());
+        assert_eq!(bytes[168..], 0xAABBCCDDEEFF0011u64.to_le_bytes());
+    }
+
+    #[test]
+    fn signed_transactions_round_trip() {
+        let keypair = account::keypair_from_passphrase(b"correct horse battery staple");
+        let mut message = Transaction::arbitrary_message(keypair.public_key, 42, Message::text("hello"), 735_000, 1000);
+        message.recipient_public_key = Some([7u8; 32]);
+        let transactions = vec![
+            Transaction::ordinary_payment(keypair.public_key, 42, 1, 735_000, 1000),
+            message,
+            Transaction::reward_recipient_assignment(keypair.public_key, 42, 735_000, 1000),
+            Transaction::commitment_add(keypair.public_key, 5_000_000_000, 735_000, 1000),
+            Transaction::commitment_remove(keypair.public_key, 5_000_000_000, 735_000, 1000),
+        ];
+        for mut transaction in transactions {
+            assert_eq!(transaction.full_hash(), None);
+            assert!(!transaction.verify());
+            transaction.sign(&keypair.agreement_key);
+            assert!(transaction.verify());
+
+            let bytes = transaction.to_bytes();
+            let parsed = Transaction::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, transaction);
+            assert_eq!(parsed.unsigned_bytes(), transaction.unsigned_bytes());
+
+            let mut sha256 = Sha256::new();
+            sha256.input(transaction.unsigned_bytes());
+            sha256.input(Sha256::digest(&transaction.signature.unwrap()));
+            assert_eq!(transaction.full_hash().unwrap()[..], sha256.result()[..]);
+            assert_eq!(transaction.id().unwrap().to_le_bytes(), transaction.full_hash().unwrap()[..8]);
+
+            assert_eq!(Transaction::from_bytes(&bytes[..bytes.len() - 1]), Err(TransactionError::Truncated));
+            let mut tampered = transaction.clone();
+            tampered.fee_nqt += 1;
+            assert!(!tampered.verify());
+        }
+    }
+
+    #[test]
+    fn attachment_bytes() {
+        let commitment = Transaction::commitment_add([1u8; 32], 0x0102, 735_000, 1000);
+        let bytes = commitment.to_bytes();
+        assert_eq!(bytes[..2], [20, 0x11]);
+        assert_eq!(bytes[40..48], [0u8; 8]);
+        assert_eq!(bytes[HEADER_SIZE..], [1, 2, 1, 0, 0, 0, 0, 0, 0]);
+
+        let message = Transaction::arbitrary_message([1u8; 32], 42, Message::binary(&[9, 9]), 735_000, 1000);
+        let bytes = message.to_bytes();
+        assert_eq!(bytes[160..164], FLAG_MESSAGE.to_le_bytes());
+        assert_eq!(bytes[HEADER_SIZE..], [1, 2, 0, 0, 0, 9, 9]);
+
+        let mut unknown = Transaction::reward_recipient_assignment([1u8; 32], 42, 735_000, 1000).to_bytes();
+        unknown[1] = 0x13;
+        assert_eq!(Transaction::from_bytes(&unknown), Err(TransactionError::UnsupportedType { transaction_type: 20, subtype: 3 }));
+        unknown.push(0);
+        unknown[1] = 0x10;
+        assert_eq!(Transaction::from_bytes(&unknown), Err(TransactionError::TrailingBytes));
+    }
+
+    #[test]
+    fn reward_recipient_assignment_from_passphrase() {
+        let transaction = assign_reward_recipient(b"correct horse battery staple", 42, 735_000, 1000, 500_000, 0xAABBCCDDEEFF0011);
+        assert!(transaction.verify());
+        assert_eq!(account::account_id_from_public_key(&transaction.sender_public_key), account::account_id_from_passphrase(b"correct horse battery staple"));
+
+        let bytes = transaction.to_bytes();
+        assert_eq!(bytes.len(), REWARD_RECIPIENT_ASSIGNMENT_SIZE);
+        assert_eq!(bytes.len(), HEADER_SIZE + 1);
+        let parsed = Transaction::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.attachment, Attachment::RewardRecipientAssignment);
+        assert_eq!((parsed.recipient_id, parsed.amount_nqt, parsed.fee_nqt), (42, 0, 735_000));
+        assert_eq!((parsed.ec_block_height, parsed.ec_block_id), (500_000, 0xAABBCCDDEEFF0011));
+    }
+}